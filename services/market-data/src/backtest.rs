@@ -0,0 +1,290 @@
+//! Deterministic backtesting venue: turns the existing `OrderBook`/`Trade`
+//! types into a simulated exchange so strategies can be validated against
+//! recorded market data using the same book structures as live trading.
+//!
+//! Market orders (and the marketable portion of a limit order) fill
+//! immediately by walking the live book, producing a partial-fill-aware
+//! average price. A limit order that rests instead joins a simulated queue:
+//! it only fills once the tape (`Trade` prints) trades through its price by
+//! more than the volume conservatively assumed to be ahead of it in the
+//! queue at the time it was accepted.
+
+use std::time::Duration;
+
+use common::{generate_client_order_id, Timestamp};
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::orderbook::{OrderBook, Trade};
+use crate::wire::Side as BookSide;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone)]
+pub struct Fill {
+    pub client_order_id: String,
+    pub price: f64,
+    pub quantity: f64,
+    pub fee: f64,
+    pub is_maker: bool,
+    pub timestamp: Timestamp,
+}
+
+/// A limit order resting in the simulated book, waiting for the tape to
+/// trade through its price.
+struct RestingOrder {
+    client_order_id: String,
+    side: Side,
+    price: f64,
+    remaining: f64,
+    /// Volume still assumed to be ahead of this order in the exchange's
+    /// real queue at `price`. Decremented as matching trades print; once it
+    /// reaches zero, further matching volume fills this order.
+    queue_ahead: f64,
+}
+
+pub struct BacktestEngine {
+    maker_fee_bps: f64,
+    taker_fee_bps: f64,
+    /// Simulated acceptance latency: `accept_time` maps a submission time
+    /// `now` to `now + latency`. The `submit_*` methods match at whatever
+    /// `accepted_at` they are given, and `on_trade` does not check it.
+    latency: Duration,
+    resting: Vec<RestingOrder>,
+    fills: Vec<Fill>,
+}
+
+impl BacktestEngine {
+    pub fn new(maker_fee_bps: f64, taker_fee_bps: f64, latency: Duration) -> Self {
+        Self {
+            maker_fee_bps,
+            taker_fee_bps,
+            latency,
+            resting: Vec::new(),
+            fills: Vec::new(),
+        }
+    }
+
+    /// The timestamp at which an order submitted "now" actually becomes
+    /// visible to the venue, after simulated latency.
+    pub fn accept_time(&self, submitted_at: Timestamp) -> Timestamp {
+        submitted_at.add_duration(self.latency)
+    }
+
+    /// Submits a market order, walking `book` to fill it immediately
+    /// (taker). Returns the client order id and the fills produced; a
+    /// quantity that exceeds available liquidity is filled as far as
+    /// possible and the rest is dropped, same as a real market order
+    /// against a thin book.
+    pub fn submit_market(&mut self, side: Side, quantity: f64, book: &OrderBook, accepted_at: Timestamp) -> (String, Vec<Fill>) {
+        let client_order_id = generate_client_order_id("backtest");
+        let fills = self.walk_book(&client_order_id, side, quantity, book, accepted_at);
+        (client_order_id, fills)
+    }
+
+    /// Submits a limit order. The portion that crosses the book fills
+    /// immediately as a taker; any remainder joins the simulated queue at
+    /// `price`, behind all resting volume already at or better than it.
+    pub fn submit_limit(
+        &mut self,
+        side: Side,
+        price: f64,
+        quantity: f64,
+        book: &OrderBook,
+        accepted_at: Timestamp,
+    ) -> (String, Vec<Fill>) {
+        let client_order_id = generate_client_order_id("backtest");
+
+        let marketable = match side {
+            Side::Buy => book.best_ask().map_or(false, |(ask, _)| ask <= price),
+            Side::Sell => book.best_bid().map_or(false, |(bid, _)| bid >= price),
+        };
+
+        let mut fills = Vec::new();
+        let mut remaining = quantity;
+        if marketable {
+            fills = self.walk_book(&client_order_id, side, quantity, book, accepted_at);
+            let filled: f64 = fills.iter().map(|f| f.quantity).sum();
+            remaining = (quantity - filled).max(0.0);
+        }
+
+        if remaining > 0.0 {
+            let book_side = match side {
+                Side::Buy => BookSide::Bid,
+                Side::Sell => BookSide::Ask,
+            };
+            let queue_ahead = book.depth_at_price(book_side, price);
+            self.resting.push(RestingOrder {
+                client_order_id: client_order_id.clone(),
+                side,
+                price,
+                remaining,
+                queue_ahead,
+            });
+        }
+
+        (client_order_id, fills)
+    }
+
+    pub fn cancel(&mut self, client_order_id: &str) -> bool {
+        let before = self.resting.len();
+        self.resting.retain(|o| o.client_order_id != client_order_id);
+        self.resting.len() != before
+    }
+
+    /// Feeds a tape print to resting orders: volume traded through a
+    /// resting order's price first drains its assumed queue position, then
+    /// fills it (as a maker) once that queue is exhausted.
+    pub fn on_trade(&mut self, trade: &Trade, now: Timestamp) -> Vec<Fill> {
+        let mut fills = Vec::new();
+        let mut remaining_volume = trade.quantity.to_f64().unwrap_or(0.0);
+
+        self.resting.retain_mut(|order| {
+            if remaining_volume <= 0.0 || !trades_through(order, trade) {
+                return true;
+            }
+
+            if order.queue_ahead > 0.0 {
+                let drained = order.queue_ahead.min(remaining_volume);
+                order.queue_ahead -= drained;
+                remaining_volume -= drained;
+                if remaining_volume <= 0.0 {
+                    return true;
+                }
+            }
+
+            let fill_qty = order.remaining.min(remaining_volume);
+            remaining_volume -= fill_qty;
+            order.remaining -= fill_qty;
+
+            fills.push(Fill {
+                client_order_id: order.client_order_id.clone(),
+                price: order.price,
+                quantity: fill_qty,
+                fee: fill_qty * order.price * self.maker_fee_bps / 10_000.0,
+                is_maker: true,
+                timestamp: now,
+            });
+
+            order.remaining > 0.0
+        });
+
+        self.fills.extend(fills.clone());
+        fills
+    }
+
+    pub fn fills(&self) -> &[Fill] {
+        &self.fills
+    }
+
+    /// Walks `book` from the touch, consuming levels opposite `side` until
+    /// `quantity` is filled or liquidity runs out, charging the taker fee.
+    fn walk_book(&mut self, client_order_id: &str, side: Side, quantity: f64, book: &OrderBook, now: Timestamp) -> Vec<Fill> {
+        let levels: Vec<(f64, f64)> = match side {
+            Side::Buy => book.asks.iter().map(|(p, q)| (p.0, *q)).collect(),
+            Side::Sell => book.bids.iter().rev().map(|(p, q)| (p.0, *q)).collect(),
+        };
+
+        let mut fills = Vec::new();
+        let mut remaining = quantity;
+        for (price, available) in levels {
+            if remaining <= 0.0 {
+                break;
+            }
+            let fill_qty = available.min(remaining);
+            remaining -= fill_qty;
+
+            fills.push(Fill {
+                client_order_id: client_order_id.to_string(),
+                price,
+                quantity: fill_qty,
+                fee: fill_qty * price * self.taker_fee_bps / 10_000.0,
+                is_maker: false,
+                timestamp: now,
+            });
+        }
+
+        self.fills.extend(fills.clone());
+        fills
+    }
+}
+
+/// Whether `trade` printed through `order`'s price on the side that would
+/// erode the real queue ahead of it: a resting buy only cares about
+/// aggressor sells trading at or below its price, and vice versa.
+fn trades_through(order: &RestingOrder, trade: &Trade) -> bool {
+    let trade_price = trade.price.to_f64().unwrap_or(0.0);
+    match order.side {
+        Side::Buy => trade.is_buyer_maker && trade_price <= order.price,
+        Side::Sell => !trade.is_buyer_maker && trade_price >= order.price,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::{DepthUpdate, PriceLevel};
+
+    fn book_with_levels(bids: &[(f64, f64)], asks: &[(f64, f64)]) -> OrderBook {
+        let mut book = OrderBook::new("BTCUSDT".to_string());
+        book.apply_update(&DepthUpdate {
+            symbol: "BTCUSDT".to_string(),
+            first_update_id: 1,
+            last_update_id: 1,
+            bids: bids.iter().map(|(p, q)| PriceLevel::new(*p, *q)).collect(),
+            asks: asks.iter().map(|(p, q)| PriceLevel::new(*p, *q)).collect(),
+        }).unwrap();
+        book
+    }
+
+    #[test]
+    fn test_market_order_walks_levels_for_partial_fill_avg_price() {
+        let book = book_with_levels(&[], &[(100.0, 1.0), (101.0, 1.0)]);
+        let mut engine = BacktestEngine::new(1.0, 5.0, Duration::from_micros(0));
+
+        let (_, fills) = engine.submit_market(Side::Buy, 1.5, &book, Timestamp::now());
+
+        assert_eq!(fills.len(), 2);
+        assert_eq!(fills[0].price, 100.0);
+        assert_eq!(fills[0].quantity, 1.0);
+        assert_eq!(fills[1].price, 101.0);
+        assert_eq!(fills[1].quantity, 0.5);
+        assert!(fills.iter().all(|f| !f.is_maker));
+    }
+
+    #[test]
+    fn test_limit_order_rests_and_fills_after_queue_trades_through() {
+        let book = book_with_levels(&[(100.0, 2.0)], &[(101.0, 1.0)]);
+        let mut engine = BacktestEngine::new(1.0, 5.0, Duration::from_micros(0));
+
+        // Joins the back of the queue at 100, behind the 2.0 already resting.
+        let (client_order_id, fills) = engine.submit_limit(Side::Buy, 100.0, 1.0, &book, Timestamp::now());
+        assert!(fills.is_empty());
+
+        let sell_trade = |qty: f64| Trade::new("BTCUSDT", 1, 100.0, qty, 0, true); // taker sold into the bid
+
+        // Drains the queue ahead (2.0) but doesn't reach our order yet.
+        let fills = engine.on_trade(&sell_trade(2.0), Timestamp::now());
+        assert!(fills.is_empty());
+
+        // Now the tape trades through our own queue position.
+        let fills = engine.on_trade(&sell_trade(0.6), Timestamp::now());
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].client_order_id, client_order_id);
+        assert_eq!(fills[0].quantity, 0.6);
+        assert!(fills[0].is_maker);
+    }
+
+    #[test]
+    fn test_cancel_removes_resting_order() {
+        let book = book_with_levels(&[(100.0, 1.0)], &[(101.0, 1.0)]);
+        let mut engine = BacktestEngine::new(1.0, 5.0, Duration::from_micros(0));
+
+        let (client_order_id, _) = engine.submit_limit(Side::Sell, 105.0, 1.0, &book, Timestamp::now());
+        assert!(engine.cancel(&client_order_id));
+        assert!(!engine.cancel(&client_order_id));
+    }
+}
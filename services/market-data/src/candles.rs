@@ -0,0 +1,454 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+use crate::orderbook::Trade;
+
+/// Candle resolution, used to bucket trades into fixed-width time windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Resolution {
+    OneMinute,
+    FiveMinutes,
+    OneHour,
+    OneDay,
+}
+
+impl Resolution {
+    pub fn all() -> [Resolution; 4] {
+        [
+            Resolution::OneMinute,
+            Resolution::FiveMinutes,
+            Resolution::OneHour,
+            Resolution::OneDay,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Resolution::OneMinute => "1m",
+            Resolution::FiveMinutes => "5m",
+            Resolution::OneHour => "1h",
+            Resolution::OneDay => "1d",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "1m" => Some(Resolution::OneMinute),
+            "5m" => Some(Resolution::FiveMinutes),
+            "1h" => Some(Resolution::OneHour),
+            "1d" => Some(Resolution::OneDay),
+            _ => None,
+        }
+    }
+
+    fn seconds(&self) -> i64 {
+        match self {
+            Resolution::OneMinute => 60,
+            Resolution::FiveMinutes => 300,
+            Resolution::OneHour => 3600,
+            Resolution::OneDay => 86400,
+        }
+    }
+
+    /// Floors a trade timestamp (epoch millis) down to this resolution's
+    /// bucket start, in epoch seconds.
+    fn bucket(&self, timestamp_ms: i64) -> i64 {
+        let seconds = timestamp_ms / 1000;
+        seconds.div_euclid(self.seconds()) * self.seconds()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Candle {
+    pub symbol: String,
+    pub resolution: String,
+    pub open_time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub base_volume: f64,
+    pub quote_volume: f64,
+    /// Base-asset volume from trades where the taker bought (`is_buyer_maker == false`).
+    pub buy_volume: f64,
+    /// Base-asset volume from trades where the taker sold (`is_buyer_maker == true`).
+    pub sell_volume: f64,
+    pub trade_count: u64,
+}
+
+struct CandleState {
+    bucket_start: i64,
+    candle: Candle,
+}
+
+impl CandleState {
+    fn open(trade: &Trade, resolution: Resolution, bucket_start: i64) -> Self {
+        // Candles are a display/aggregation artifact, so we drop to f64 as
+        // soon as a trade enters candle math rather than threading Decimal
+        // through OHLCV accumulation.
+        let price = trade.price.to_f64().unwrap_or(0.0);
+        let quantity = trade.quantity.to_f64().unwrap_or(0.0);
+        let (buy_volume, sell_volume) = if trade.is_buyer_maker {
+            (0.0, quantity)
+        } else {
+            (quantity, 0.0)
+        };
+        Self {
+            bucket_start,
+            candle: Candle {
+                symbol: trade.symbol.clone(),
+                resolution: resolution.as_str().to_string(),
+                open_time: bucket_start,
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                base_volume: quantity,
+                quote_volume: price * quantity,
+                buy_volume,
+                sell_volume,
+                trade_count: 1,
+            },
+        }
+    }
+}
+
+/// A zero-volume candle for a bucket no trade landed in, pinned open/high/low/close
+/// to the prior bucket's close so a gap in trading doesn't leave a hole in the series.
+fn flat_candle(symbol: &str, resolution: Resolution, bucket_start: i64, price: f64) -> Candle {
+    Candle {
+        symbol: symbol.to_string(),
+        resolution: resolution.as_str().to_string(),
+        open_time: bucket_start,
+        open: price,
+        high: price,
+        low: price,
+        close: price,
+        base_volume: 0.0,
+        quote_volume: 0.0,
+        buy_volume: 0.0,
+        sell_volume: 0.0,
+        trade_count: 0,
+    }
+}
+
+type CandleKey = (String, Resolution);
+
+/// Rolls a `Trade` stream into OHLCV candles at several fixed resolutions,
+/// keyed by `(symbol, resolution)`. A bucket stays open and accumulates
+/// high/low/volume until a trade lands in a later bucket, at which point it
+/// closes into history and a fresh candle opens.
+pub struct CandleAggregator {
+    active: RwLock<HashMap<CandleKey, CandleState>>,
+    history: RwLock<HashMap<CandleKey, Vec<Candle>>>,
+    max_history: usize,
+}
+
+impl CandleAggregator {
+    pub fn new(max_history: usize) -> Self {
+        Self {
+            active: RwLock::new(HashMap::new()),
+            history: RwLock::new(HashMap::new()),
+            max_history,
+        }
+    }
+
+    /// Rolls a single trade into every tracked resolution for its symbol,
+    /// returning every candle this trade's bucket crossing closed: the
+    /// previously active candle, plus a flat candle for each bucket skipped
+    /// entirely (e.g. a quiet market with no trades for a few minutes), so
+    /// the closed series has no holes.
+    pub fn record_trade(&self, trade: &Trade) -> Vec<Candle> {
+        Resolution::all()
+            .into_iter()
+            .flat_map(|resolution| self.apply(trade, resolution))
+            .collect()
+    }
+
+    /// The currently open (not yet closed) candle for `symbol`/`resolution`,
+    /// if any trade has landed in it yet.
+    pub fn active_candle(&self, symbol: &str, resolution: Resolution) -> Option<Candle> {
+        let key = (symbol.to_string(), resolution);
+        self.active.read().unwrap().get(&key).map(|state| state.candle.clone())
+    }
+
+    /// Replays a historical vector of trades, in order, to reconstruct
+    /// candle history on startup (e.g. from a journal or REST backfill).
+    /// Returns every `1m` candle the replay closed, oldest first, so a
+    /// burst of backfilled trades can be persisted in one batch rather than
+    /// one candle at a time.
+    pub fn backfill(&self, trades: &[Trade]) -> Vec<Candle> {
+        trades
+            .iter()
+            .flat_map(|trade| self.record_trade(trade))
+            .filter(|candle| candle.resolution == Resolution::OneMinute.as_str())
+            .collect()
+    }
+
+    /// Closed candles for `symbol`/`resolution` whose `open_time` falls in
+    /// `[from, to]` (epoch seconds), oldest first, plus the in-progress
+    /// candle if it's in range too.
+    pub fn candles(&self, symbol: &str, resolution: Resolution, from: i64, to: i64) -> Vec<Candle> {
+        let key = (symbol.to_string(), resolution);
+
+        let mut candles: Vec<Candle> = self
+            .history
+            .read()
+            .unwrap()
+            .get(&key)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter(|c| c.open_time >= from && c.open_time <= to)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if let Some(state) = self.active.read().unwrap().get(&key) {
+            if state.bucket_start >= from && state.bucket_start <= to {
+                candles.push(state.candle.clone());
+            }
+        }
+
+        candles
+    }
+
+    /// Rolls `trade` into the active candle for `resolution`, returning every
+    /// candle this trade's bucket crossing closed: the previously active
+    /// candle, plus a flat candle (zero volume, OHLC pinned to its close) for
+    /// each bucket skipped entirely between it and `trade`'s bucket.
+    fn apply(&self, trade: &Trade, resolution: Resolution) -> Vec<Candle> {
+        let bucket_start = resolution.bucket(trade.timestamp);
+        let key: CandleKey = (trade.symbol.clone(), resolution);
+        let price = trade.price.to_f64().unwrap_or(0.0);
+        let quantity = trade.quantity.to_f64().unwrap_or(0.0);
+
+        let mut active = self.active.write().unwrap();
+
+        let should_close = matches!(active.get(&key), Some(state) if state.bucket_start < bucket_start);
+        let mut closed = Vec::new();
+        if should_close {
+            if let Some(state) = active.remove(&key) {
+                let prior_close = state.candle.close;
+                let mut gap_start = state.bucket_start + resolution.seconds();
+                closed.push(state.candle.clone());
+                self.close_candle(&key, state.candle);
+
+                while gap_start < bucket_start {
+                    let flat = flat_candle(&trade.symbol, resolution, gap_start, prior_close);
+                    closed.push(flat.clone());
+                    self.close_candle(&key, flat);
+                    gap_start += resolution.seconds();
+                }
+            }
+        }
+
+        match active.get_mut(&key) {
+            Some(state) => {
+                let candle = &mut state.candle;
+                candle.high = candle.high.max(price);
+                candle.low = candle.low.min(price);
+                if bucket_start >= state.bucket_start {
+                    candle.close = price;
+                }
+                candle.base_volume += quantity;
+                candle.quote_volume += price * quantity;
+                if trade.is_buyer_maker {
+                    candle.sell_volume += quantity;
+                } else {
+                    candle.buy_volume += quantity;
+                }
+                candle.trade_count += 1;
+            }
+            None => {
+                active.insert(key, CandleState::open(trade, resolution, bucket_start));
+            }
+        }
+
+        closed
+    }
+
+    fn close_candle(&self, key: &CandleKey, candle: Candle) {
+        let mut history = self.history.write().unwrap();
+        let entries = history.entry(key.clone()).or_default();
+        entries.push(candle);
+
+        if entries.len() > self.max_history {
+            let excess = entries.len() - self.max_history;
+            entries.drain(0..excess);
+        }
+    }
+}
+
+/// Rolls up a contiguous, oldest-first run of closed lower-resolution
+/// candles into one `resolution` candle (e.g. twelve `5m` candles into one
+/// `1h`), so coarser timeframes can be derived from finer ones instead of
+/// re-scanning the trade tape.
+pub fn rollup(resolution: Resolution, candles: &[Candle]) -> Option<Candle> {
+    let first = candles.first()?;
+    let last = candles.last()?;
+
+    Some(Candle {
+        symbol: first.symbol.clone(),
+        resolution: resolution.as_str().to_string(),
+        open_time: resolution.bucket(first.open_time * 1000),
+        open: first.open,
+        high: candles.iter().fold(f64::MIN, |acc, c| acc.max(c.high)),
+        low: candles.iter().fold(f64::MAX, |acc, c| acc.min(c.low)),
+        close: last.close,
+        base_volume: candles.iter().map(|c| c.base_volume).sum(),
+        quote_volume: candles.iter().map(|c| c.quote_volume).sum(),
+        buy_volume: candles.iter().map(|c| c.buy_volume).sum(),
+        sell_volume: candles.iter().map(|c| c.sell_volume).sum(),
+        trade_count: candles.iter().map(|c| c.trade_count).sum(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(symbol: &str, price: f64, quantity: f64, timestamp: i64) -> Trade {
+        trade_with_side(symbol, price, quantity, timestamp, false)
+    }
+
+    fn trade_with_side(
+        symbol: &str,
+        price: f64,
+        quantity: f64,
+        timestamp: i64,
+        is_buyer_maker: bool,
+    ) -> Trade {
+        Trade::new(symbol, 1, price, quantity, timestamp, is_buyer_maker)
+    }
+
+    #[test]
+    fn test_single_bucket_aggregates_ohlcv() {
+        let aggregator = CandleAggregator::new(100);
+
+        aggregator.record_trade(&trade("BTCUSDT", 100.0, 1.0, 0));
+        aggregator.record_trade(&trade("BTCUSDT", 105.0, 2.0, 10_000));
+        aggregator.record_trade(&trade("BTCUSDT", 95.0, 1.0, 20_000));
+
+        let candles = aggregator.candles("BTCUSDT", Resolution::OneMinute, 0, 59);
+        assert_eq!(candles.len(), 1);
+        let candle = &candles[0];
+        assert_eq!(candle.open, 100.0);
+        assert_eq!(candle.high, 105.0);
+        assert_eq!(candle.low, 95.0);
+        assert_eq!(candle.close, 95.0);
+        assert_eq!(candle.base_volume, 4.0);
+        assert_eq!(candle.quote_volume, 100.0 + 210.0 + 95.0);
+    }
+
+    #[test]
+    fn test_later_trade_closes_previous_bucket() {
+        let aggregator = CandleAggregator::new(100);
+
+        aggregator.record_trade(&trade("BTCUSDT", 100.0, 1.0, 0));
+        aggregator.record_trade(&trade("BTCUSDT", 110.0, 1.0, 61_000));
+
+        let closed = aggregator.candles("BTCUSDT", Resolution::OneMinute, 0, 0);
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].close, 100.0);
+
+        let active = aggregator.candles("BTCUSDT", Resolution::OneMinute, 60, 60);
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].open, 110.0);
+    }
+
+    #[test]
+    fn test_skipped_buckets_are_backfilled_with_flat_candles() {
+        let aggregator = CandleAggregator::new(100);
+
+        aggregator.record_trade(&trade("BTCUSDT", 100.0, 1.0, 0));
+        let closed = aggregator.record_trade(&trade("BTCUSDT", 110.0, 1.0, 241_000)); // 4 buckets later
+
+        assert_eq!(closed.len(), 4);
+        assert_eq!(closed[0].close, 100.0); // the real candle
+        for flat in &closed[1..] {
+            assert_eq!(flat.open, 100.0);
+            assert_eq!(flat.close, 100.0);
+            assert_eq!(flat.base_volume, 0.0);
+            assert_eq!(flat.trade_count, 0);
+        }
+        assert_eq!(closed[1].open_time, 60);
+        assert_eq!(closed[2].open_time, 120);
+        assert_eq!(closed[3].open_time, 180);
+
+        let candles = aggregator.candles("BTCUSDT", Resolution::OneMinute, 0, 180);
+        assert_eq!(candles.len(), 4);
+    }
+
+    #[test]
+    fn test_backfill_reconstructs_history() {
+        let aggregator = CandleAggregator::new(100);
+        let trades = vec![
+            trade("ETHUSDT", 10.0, 1.0, 0),
+            trade("ETHUSDT", 20.0, 1.0, 70_000),
+            trade("ETHUSDT", 30.0, 1.0, 140_000),
+        ];
+
+        let closed = aggregator.backfill(&trades);
+        assert_eq!(closed.len(), 2);
+        assert_eq!(closed[0].open, 10.0);
+        assert_eq!(closed[1].open, 20.0);
+
+        // Two closed candles plus the still-open one at 120.
+        let candles = aggregator.candles("ETHUSDT", Resolution::OneMinute, 0, 120);
+        assert_eq!(candles.len(), 3);
+        assert_eq!(candles[0].open, 10.0);
+        assert_eq!(candles[1].open, 20.0);
+        assert_eq!(candles[2].open, 30.0);
+    }
+
+    #[test]
+    fn test_buy_sell_volume_and_trade_count_split_on_is_buyer_maker() {
+        let aggregator = CandleAggregator::new(100);
+
+        aggregator.record_trade(&trade_with_side("BTCUSDT", 100.0, 1.0, 0, false)); // taker bought
+        aggregator.record_trade(&trade_with_side("BTCUSDT", 101.0, 2.0, 1_000, true)); // taker sold
+
+        let candles = aggregator.candles("BTCUSDT", Resolution::OneMinute, 0, 59);
+        assert_eq!(candles.len(), 1);
+        assert_eq!(candles[0].buy_volume, 1.0);
+        assert_eq!(candles[0].sell_volume, 2.0);
+        assert_eq!(candles[0].trade_count, 2);
+    }
+
+    #[test]
+    fn test_rollup_combines_lower_resolution_candles() {
+        let aggregator = CandleAggregator::new(100);
+
+        aggregator.record_trade(&trade("BTCUSDT", 100.0, 1.0, 0));
+        aggregator.record_trade(&trade("BTCUSDT", 90.0, 1.0, 61_000));
+        aggregator.record_trade(&trade("BTCUSDT", 120.0, 1.0, 121_000));
+        aggregator.record_trade(&trade("BTCUSDT", 80.0, 1.0, 400_000)); // closes the 5m bucket
+
+        let fives = aggregator.candles("BTCUSDT", Resolution::FiveMinutes, 0, 0);
+        assert_eq!(fives.len(), 1);
+
+        let rolled = rollup(Resolution::OneHour, &fives).unwrap();
+        assert_eq!(rolled.open, 100.0);
+        assert_eq!(rolled.high, 120.0);
+        assert_eq!(rolled.low, 90.0);
+        assert_eq!(rolled.close, fives[0].close);
+        assert_eq!(rolled.base_volume, fives[0].base_volume);
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let aggregator = CandleAggregator::new(2);
+
+        for i in 0..5i64 {
+            aggregator.record_trade(&trade("BTCUSDT", 100.0 + i as f64, 1.0, i * 61_000));
+        }
+
+        let candles = aggregator.candles("BTCUSDT", Resolution::OneMinute, 0, i64::MAX);
+        assert!(candles.len() <= 3); // 2 history + 1 active
+    }
+}
@@ -5,26 +5,81 @@ use anyhow::Result;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub symbols: Vec<String>,
+    /// Which `SnapshotSource` to fetch REST depth snapshots from:
+    /// "binance", "coinbase", or "kraken". The per-symbol WebSocket path also
+    /// builds its stream adapter from this value, and `exchange::adapter_for`
+    /// accepts only "binance" and "kraken".
+    pub exchange: String,
     pub exchange_ws_url: String,
+    pub exchange_rest_url: String,
     pub redis_url: String,
     pub order_book_depth: usize,
     pub health_port: u16,
+    pub dashboard_ws_port: u16,
     pub shm_name: String,
     pub reconnect_delay_ms: u64,
     pub max_reconnect_delay_ms: u64,
+    pub candle_history_size: usize,
+    /// Whether to open an authenticated user-data-stream connection
+    /// (account/order events) alongside the public market-data streams.
+    pub user_data_enabled: bool,
+    /// API key sent as `X-MBX-APIKEY` when requesting/refreshing the
+    /// user-data-stream `listenKey`. Required when `user_data_enabled` is set.
+    pub binance_api_key: String,
+    /// When set, all symbols share a single combined-stream WebSocket
+    /// connection (`MultiStreamClient`) instead of one connection per symbol.
+    pub multi_stream_enabled: bool,
+    /// Kline/candlestick interval to subscribe to (e.g. "1m", "5m"), used to
+    /// build the `@kline_<interval>` stream name alongside depth/aggTrade.
+    pub kline_interval: String,
+    /// Width, in seconds, of the trailing volume/high/low window `Publisher`
+    /// maintains per symbol for `market_data:SYMBOL`. Defaults to 24h but can
+    /// be narrowed (e.g. to expose 1h/4h stats) per deployment.
+    pub ticker_window_secs: u64,
+    /// Whether to additionally mirror order books and trades to a NATS
+    /// JetStream sink alongside the always-on Redis/shared-memory path, e.g.
+    /// while migrating consumers over to NATS.
+    pub nats_enabled: bool,
+    pub nats_url: String,
+    /// JetStream stream name the NATS sink creates (if missing) and
+    /// publishes into.
+    pub nats_stream_name: String,
+    /// Whether to persist every trade and finalized candle into
+    /// Postgres/TimescaleDB (`storage::StorageWriter`) for historical range
+    /// queries and candle backfill-on-restart.
+    pub storage_enabled: bool,
+    pub database_url: String,
+    /// Rows buffered per table before `StorageWriter` flushes early, ahead
+    /// of its periodic timer-driven flush.
+    pub storage_batch_size: usize,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             symbols: vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()],
+            exchange: "binance".to_string(),
             exchange_ws_url: "wss://fstream.binance.com/stream".to_string(),
+            exchange_rest_url: "https://fapi.binance.com".to_string(),
             redis_url: "redis://127.0.0.1:6379".to_string(),
             order_book_depth: 20,
             health_port: 9090,
+            dashboard_ws_port: 8090,
             shm_name: "market_data_shm".to_string(),
             reconnect_delay_ms: 1000,
             max_reconnect_delay_ms: 60000,
+            candle_history_size: 1000,
+            user_data_enabled: false,
+            binance_api_key: String::new(),
+            multi_stream_enabled: false,
+            kline_interval: "1m".to_string(),
+            ticker_window_secs: 24 * 60 * 60,
+            nats_enabled: false,
+            nats_url: "nats://127.0.0.1:4222".to_string(),
+            nats_stream_name: "market-data".to_string(),
+            storage_enabled: false,
+            database_url: "postgres://localhost/market_data".to_string(),
+            storage_batch_size: 200,
         }
     }
 }
@@ -0,0 +1,156 @@
+use std::sync::{Arc, RwLock};
+
+use anyhow::{Context, Result};
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::metrics;
+use crate::orderbook::{DepthUpdate, OrderBook};
+use crate::snapshot::SnapshotSource;
+
+/// REST snapshot depth requested while synchronizing. Binance's documented
+/// diff-depth procedure recommends the maximum limit so the snapshot is
+/// likely to bridge cleanly with the buffered diff stream.
+const SNAPSHOT_LIMIT: usize = 1000;
+
+/// A book emitted by [`DepthSynchronizer::run`], tagged with whether it came
+/// from an ordinary diff apply or a REST resync. A resync rebuilds the book
+/// from scratch rather than applying one more delta on top of what a
+/// consumer already has, so it needs to be republished as a full checkpoint
+/// rather than diffed against the consumer's previous (now-stale) copy.
+pub enum BookUpdate {
+    Applied(OrderBook),
+    Resynced(OrderBook),
+}
+
+/// Keeps an `OrderBook` continuously correct by merging Binance's `@depth`
+/// diff stream with a REST snapshot, following the procedure Binance
+/// documents for synchronizing a local book:
+///
+/// 1. Buffer incoming diff events before fetching a snapshot.
+/// 2. Fetch the snapshot and read its `lastUpdateId`.
+/// 3. Discard any buffered event whose `u < lastUpdateId`.
+/// 4. The first event applied must satisfy `U <= lastUpdateId + 1 <= u`;
+///    otherwise refetch the snapshot and retry.
+/// 5. Apply every later event in order, resyncing from scratch on any gap.
+pub struct DepthSynchronizer {
+    symbol: String,
+    fetcher: Arc<dyn SnapshotSource>,
+    book: Arc<RwLock<OrderBook>>,
+}
+
+impl DepthSynchronizer {
+    pub fn new(symbol: String, fetcher: Arc<dyn SnapshotSource>) -> Self {
+        let book = Arc::new(RwLock::new(OrderBook::new(symbol.clone())));
+        Self {
+            symbol,
+            fetcher,
+            book,
+        }
+    }
+
+    /// Shared handle to the continuously-synced order book.
+    pub fn book(&self) -> Arc<RwLock<OrderBook>> {
+        Arc::clone(&self.book)
+    }
+
+    /// Drives the synchronization loop: resyncs against a REST snapshot,
+    /// then applies each diff from `events` in order, pushing the refreshed
+    /// book to `updates` after every successful apply. Returns once
+    /// `events` closes.
+    pub async fn run(
+        &self,
+        mut events: mpsc::Receiver<DepthUpdate>,
+        updates: mpsc::Sender<BookUpdate>,
+    ) -> Result<()> {
+        self.resync(&mut events, None).await?;
+        let _ = updates.send(BookUpdate::Resynced(self.snapshot())).await;
+
+        loop {
+            let Some(event) = events.recv().await else {
+                return Ok(());
+            };
+
+            let applied = self.book.write().unwrap().apply_update(&event);
+
+            match applied {
+                Ok(()) => {
+                    let _ = updates.send(BookUpdate::Applied(self.snapshot())).await;
+                }
+                Err(e) => {
+                    warn!(
+                        "{}: {}, resynchronizing from a fresh snapshot",
+                        self.symbol, e
+                    );
+                    metrics::SEQUENCE_ERRORS
+                        .with_label_values(&[&self.symbol])
+                        .inc();
+                    self.resync(&mut events, Some(event)).await?;
+                    let _ = updates.send(BookUpdate::Resynced(self.snapshot())).await;
+                }
+            }
+        }
+    }
+
+    fn snapshot(&self) -> OrderBook {
+        self.book.read().unwrap().clone()
+    }
+
+    /// Implements steps 1-4: buffers `events` (seeded with `pending` when a
+    /// gap triggered this resync) while fetching a REST snapshot, discards
+    /// events that predate it, then applies the diff that bridges the
+    /// snapshot to the live stream plus everything buffered after it.
+    async fn resync(
+        &self,
+        events: &mut mpsc::Receiver<DepthUpdate>,
+        pending: Option<DepthUpdate>,
+    ) -> Result<()> {
+        metrics::RESYNCS_TOTAL.with_label_values(&[&self.symbol]).inc();
+        let mut buffered: Vec<DepthUpdate> = pending.into_iter().collect();
+
+        loop {
+            let snapshot = self
+                .fetcher
+                .fetch_snapshot(&self.symbol, SNAPSHOT_LIMIT)
+                .await
+                .context("failed to fetch depth snapshot during resync")?;
+
+            // (3) discard anything that predates the snapshot.
+            buffered.retain(|e| e.last_update_id >= snapshot.last_update_id);
+
+            // (1) keep buffering until some event could bridge the snapshot.
+            while !buffered
+                .iter()
+                .any(|e| e.last_update_id >= snapshot.last_update_id + 1)
+            {
+                match events.recv().await {
+                    Some(event) => buffered.push(event),
+                    None => anyhow::bail!("depth event stream closed during resync"),
+                }
+            }
+
+            let bridge_idx = buffered
+                .iter()
+                .position(|e| e.last_update_id >= snapshot.last_update_id + 1)
+                .expect("loop above guarantees a bridging event exists");
+
+            // (4) the bridging event must also start at or before lastUpdateId + 1.
+            if buffered[bridge_idx].first_update_id > snapshot.last_update_id + 1 {
+                warn!(
+                    "{}: buffered event U={} does not bridge snapshot lastUpdateId={}, refetching",
+                    self.symbol, buffered[bridge_idx].first_update_id, snapshot.last_update_id
+                );
+                continue;
+            }
+
+            let mut book = snapshot;
+            for event in buffered.split_off(bridge_idx) {
+                book.apply_update(&event).map_err(|e| anyhow::anyhow!(e))?;
+            }
+            buffered.clear();
+
+            *self.book.write().unwrap() = book;
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,554 @@
+use anyhow::{Context, Result};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::orderbook::{DepthUpdate, PriceLevel, Trade};
+
+/// One normalized message parsed out of a venue's wire frame. `WebSocketClient`
+/// only ever sees these, never a venue's raw JSON shape.
+#[derive(Debug)]
+pub enum ExchangeMessage {
+    Depth(DepthUpdate),
+    Trade(Trade),
+    Kline(Kline),
+    BookTicker(BookTicker),
+}
+
+/// A kline/candlestick update from the exchange's native candle stream,
+/// carried through at full precision. Distinct from `Candle` (`candles.rs`),
+/// which `CandleAggregator` derives locally from the trade stream rather
+/// than taking the exchange's own OHLCV as given.
+#[derive(Debug, Clone, Serialize)]
+pub struct Kline {
+    pub symbol: String,
+    pub interval: String,
+    pub open_time: i64,
+    pub close_time: i64,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub volume: Decimal,
+    /// Whether this bucket is finalized or still live.
+    pub is_closed: bool,
+}
+
+/// Best bid/ask from the exchange's top-of-book stream: a lower-latency
+/// signal than waiting on the next full depth diff.
+#[derive(Debug, Clone, Serialize)]
+pub struct BookTicker {
+    pub symbol: String,
+    pub update_id: u64,
+    pub bid_price: Decimal,
+    pub bid_qty: Decimal,
+    pub ask_price: Decimal,
+    pub ask_qty: Decimal,
+}
+
+/// Everything venue-specific about a WebSocket feed, so `WebSocketClient`'s
+/// connection/reconnect/metrics loop can stay exchange-agnostic. Modeled on
+/// the pluggable `SnapshotSource` trait in `snapshot.rs`.
+pub trait ExchangeAdapter: Send + Sync {
+    /// Short venue name, used only for logging.
+    fn name(&self) -> &'static str;
+
+    /// Builds the full WebSocket URL to open for `symbol`.
+    fn subscribe_url(&self, ws_url: &str, symbol: &str, kline_interval: &str) -> String;
+
+    /// Control messages to send right after connecting, e.g. Kraken's
+    /// explicit `{"event":"subscribe",...}` frames. Venues that encode the
+    /// subscription entirely in the URL (Binance) send none.
+    fn subscribe_messages(&self, _symbol: &str, _kline_interval: &str) -> Vec<Message> {
+        Vec::new()
+    }
+
+    /// Parses one inbound text frame into zero or more normalized messages.
+    /// Control/ack/status frames that carry no market data parse to an
+    /// empty `Vec` rather than an error.
+    fn parse_frame(&self, text: &str) -> Result<Vec<ExchangeMessage>>;
+
+    /// A frame this client should send on a fixed interval to keep the
+    /// connection alive. `None` for venues that push their own heartbeat
+    /// frames instead (Kraken) - those are caught by `heartbeat_timeout`.
+    fn outbound_ping(&self) -> Option<Message> {
+        None
+    }
+
+    /// Longest gap allowed between inbound frames before the connection is
+    /// considered dead and the caller's reconnect/backoff loop takes over.
+    fn heartbeat_timeout(&self) -> Duration {
+        Duration::from_secs(60)
+    }
+}
+
+/// Builds the `ExchangeAdapter` named by `exchange` ("binance" or "kraken").
+pub fn adapter_for(exchange: &str) -> Result<Arc<dyn ExchangeAdapter>> {
+    match exchange {
+        "binance" => Ok(Arc::new(BinanceAdapter)),
+        "kraken" => Ok(Arc::new(KrakenAdapter::new())),
+        other => anyhow::bail!("unknown exchange '{}'", other),
+    }
+}
+
+// --- Binance ----------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct BinanceWrapper {
+    stream: String,
+    data: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceDepthUpdate {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "U")]
+    first_update_id: u64,
+    #[serde(rename = "u")]
+    last_update_id: u64,
+    #[serde(rename = "b")]
+    bids: Vec<(String, String)>, // [price, quantity]
+    #[serde(rename = "a")]
+    asks: Vec<(String, String)>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceAggTrade {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "a")]
+    trade_id: u64,
+    #[serde(rename = "p")]
+    price: String,
+    #[serde(rename = "q")]
+    quantity: String,
+    #[serde(rename = "T")]
+    timestamp: i64,
+    #[serde(rename = "m")]
+    is_buyer_maker: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceKlineEvent {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "k")]
+    kline: BinanceKlineData,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceKlineData {
+    #[serde(rename = "t")]
+    open_time: i64,
+    #[serde(rename = "T")]
+    close_time: i64,
+    #[serde(rename = "i")]
+    interval: String,
+    #[serde(rename = "o")]
+    open: String,
+    #[serde(rename = "h")]
+    high: String,
+    #[serde(rename = "l")]
+    low: String,
+    #[serde(rename = "c")]
+    close: String,
+    #[serde(rename = "v")]
+    volume: String,
+    #[serde(rename = "x")]
+    is_closed: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceBookTicker {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "u")]
+    update_id: u64,
+    #[serde(rename = "b")]
+    bid_price: String,
+    #[serde(rename = "B")]
+    bid_qty: String,
+    #[serde(rename = "a")]
+    ask_price: String,
+    #[serde(rename = "A")]
+    ask_qty: String,
+}
+
+/// Binance USD-M futures combined-stream feed: depth/aggTrade/kline/bookTicker
+/// multiplexed over a single `wss://.../stream?streams=...` connection.
+pub struct BinanceAdapter;
+
+impl ExchangeAdapter for BinanceAdapter {
+    fn name(&self) -> &'static str {
+        "binance"
+    }
+
+    fn subscribe_url(&self, ws_url: &str, symbol: &str, kline_interval: &str) -> String {
+        let symbol_lower = symbol.to_lowercase();
+        let streams = format!(
+            "{symbol}@depth@100ms/{symbol}@aggTrade/{symbol}@kline_{interval}/{symbol}@bookTicker",
+            symbol = symbol_lower,
+            interval = kline_interval,
+        );
+        format!("{}?streams={}", ws_url, streams)
+    }
+
+    fn parse_frame(&self, text: &str) -> Result<Vec<ExchangeMessage>> {
+        // Subscription acks and other control frames don't carry a "stream"
+        // field; treat anything that doesn't parse as the combined-stream
+        // wrapper as a harmless frame to ignore rather than an error.
+        let wrapper: BinanceWrapper = match serde_json::from_str(text) {
+            Ok(wrapper) => wrapper,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        if wrapper.stream.contains("depth") {
+            let update: BinanceDepthUpdate = serde_json::from_value(wrapper.data)
+                .context("Failed to parse depth update")?;
+            let depth_update = DepthUpdate {
+                symbol: update.symbol,
+                first_update_id: update.first_update_id,
+                last_update_id: update.last_update_id,
+                bids: update
+                    .bids
+                    .iter()
+                    .map(|(p, q)| PriceLevel::parse(p, q))
+                    .collect::<Result<Vec<_>, _>>()
+                    .context("invalid bid price/quantity in depth update")?,
+                asks: update
+                    .asks
+                    .iter()
+                    .map(|(p, q)| PriceLevel::parse(p, q))
+                    .collect::<Result<Vec<_>, _>>()
+                    .context("invalid ask price/quantity in depth update")?,
+            };
+            Ok(vec![ExchangeMessage::Depth(depth_update)])
+        } else if wrapper.stream.contains("aggTrade") {
+            let trade_data: BinanceAggTrade = serde_json::from_value(wrapper.data)
+                .context("Failed to parse trade")?;
+            let trade = Trade::parse(
+                trade_data.symbol,
+                trade_data.trade_id,
+                &trade_data.price,
+                &trade_data.quantity,
+                trade_data.timestamp,
+                trade_data.is_buyer_maker,
+            )
+            .context("invalid price/quantity in trade")?;
+            Ok(vec![ExchangeMessage::Trade(trade)])
+        } else if wrapper.stream.contains("kline") {
+            let event: BinanceKlineEvent = serde_json::from_value(wrapper.data)
+                .context("Failed to parse kline")?;
+            let k = event.kline;
+            let kline = Kline {
+                symbol: event.symbol,
+                interval: k.interval,
+                open_time: k.open_time,
+                close_time: k.close_time,
+                open: Decimal::from_str(&k.open).context("invalid kline open")?,
+                high: Decimal::from_str(&k.high).context("invalid kline high")?,
+                low: Decimal::from_str(&k.low).context("invalid kline low")?,
+                close: Decimal::from_str(&k.close).context("invalid kline close")?,
+                volume: Decimal::from_str(&k.volume).context("invalid kline volume")?,
+                is_closed: k.is_closed,
+            };
+            Ok(vec![ExchangeMessage::Kline(kline)])
+        } else if wrapper.stream.contains("bookTicker") {
+            let ticker_data: BinanceBookTicker = serde_json::from_value(wrapper.data)
+                .context("Failed to parse book ticker")?;
+            let ticker = BookTicker {
+                symbol: ticker_data.symbol,
+                update_id: ticker_data.update_id,
+                bid_price: Decimal::from_str(&ticker_data.bid_price)
+                    .context("invalid book ticker bid price")?,
+                bid_qty: Decimal::from_str(&ticker_data.bid_qty)
+                    .context("invalid book ticker bid qty")?,
+                ask_price: Decimal::from_str(&ticker_data.ask_price)
+                    .context("invalid book ticker ask price")?,
+                ask_qty: Decimal::from_str(&ticker_data.ask_qty)
+                    .context("invalid book ticker ask qty")?,
+            };
+            Ok(vec![ExchangeMessage::BookTicker(ticker)])
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    fn outbound_ping(&self) -> Option<Message> {
+        Some(Message::Ping(vec![]))
+    }
+}
+
+// --- Kraken -------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct KrakenEventFrame {
+    event: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct KrakenBookPayload {
+    #[serde(rename = "a")]
+    ask_updates: Option<Vec<(String, String, serde_json::Value)>>,
+    #[serde(rename = "b")]
+    bid_updates: Option<Vec<(String, String, serde_json::Value)>>,
+    #[serde(rename = "as")]
+    ask_snapshot: Option<Vec<(String, String, serde_json::Value)>>,
+    #[serde(rename = "bs")]
+    bid_snapshot: Option<Vec<(String, String, serde_json::Value)>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KrakenTickerPayload {
+    #[serde(rename = "a")]
+    ask: Vec<String>,
+    #[serde(rename = "b")]
+    bid: Vec<String>,
+}
+
+/// Kraken's public WebSocket v1 feed: subscription is a JSON control message
+/// sent after connecting rather than baked into the URL, and data frames are
+/// untagged JSON arrays shaped `[channelID, payload, channelName, pair]`.
+pub struct KrakenAdapter {
+    // Kraken's book/ticker channels carry no update-id of their own, so a
+    // local monotonic counter stands in for Binance's `U`/`u` - each depth
+    // frame gets `first_update_id == last_update_id`, which is always
+    // contiguous with the previous frame and so never looks like a gap to
+    // `OrderBookManager`.
+    next_seq: AtomicU64,
+    next_trade_id: AtomicU64,
+    next_ticker_id: AtomicU64,
+}
+
+impl KrakenAdapter {
+    pub fn new() -> Self {
+        Self {
+            next_seq: AtomicU64::new(1),
+            next_trade_id: AtomicU64::new(1),
+            next_ticker_id: AtomicU64::new(1),
+        }
+    }
+
+    fn levels(
+        pairs: Option<Vec<(String, String, serde_json::Value)>>,
+    ) -> Result<Vec<PriceLevel>> {
+        pairs
+            .unwrap_or_default()
+            .iter()
+            .map(|(price, quantity, _)| PriceLevel::parse(price, quantity))
+            .collect::<Result<Vec<_>, _>>()
+            .context("invalid price/quantity in Kraken book payload")
+    }
+}
+
+impl Default for KrakenAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExchangeAdapter for KrakenAdapter {
+    fn name(&self) -> &'static str {
+        "kraken"
+    }
+
+    fn subscribe_url(&self, ws_url: &str, _symbol: &str, _kline_interval: &str) -> String {
+        // Kraken doesn't encode the subscription in the URL at all; the
+        // pair/channel selection happens via `subscribe_messages` instead.
+        ws_url.to_string()
+    }
+
+    fn subscribe_messages(&self, symbol: &str, _kline_interval: &str) -> Vec<Message> {
+        ["book", "trade", "ticker"]
+            .iter()
+            .map(|channel| {
+                Message::Text(
+                    serde_json::json!({
+                        "event": "subscribe",
+                        "pair": [symbol],
+                        "subscription": { "name": channel },
+                    })
+                    .to_string(),
+                )
+            })
+            .collect()
+    }
+
+    fn parse_frame(&self, text: &str) -> Result<Vec<ExchangeMessage>> {
+        let value: serde_json::Value =
+            serde_json::from_str(text).context("Failed to parse Kraken frame")?;
+
+        // Control frames (`systemStatus`, `subscriptionStatus`, `heartbeat`)
+        // arrive as a tagged object and carry no market data.
+        if value.is_object() {
+            let _: KrakenEventFrame =
+                serde_json::from_value(value).context("Failed to parse Kraken event frame")?;
+            return Ok(Vec::new());
+        }
+
+        let frame = value
+            .as_array()
+            .context("Kraken data frame was neither an event object nor an array")?;
+        let channel_name = frame
+            .get(2)
+            .and_then(|v| v.as_str())
+            .context("Kraken data frame missing channel name")?;
+        let pair = frame
+            .get(3)
+            .and_then(|v| v.as_str())
+            .context("Kraken data frame missing pair")?
+            .to_string();
+        let payload = frame
+            .get(1)
+            .cloned()
+            .context("Kraken data frame missing payload")?;
+
+        if channel_name.starts_with("book") {
+            let book: KrakenBookPayload =
+                serde_json::from_value(payload).context("Failed to parse Kraken book payload")?;
+            let bids = Self::levels(book.bid_updates.or(book.bid_snapshot))?;
+            let asks = Self::levels(book.ask_updates.or(book.ask_snapshot))?;
+            if bids.is_empty() && asks.is_empty() {
+                return Ok(Vec::new());
+            }
+            let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+            Ok(vec![ExchangeMessage::Depth(DepthUpdate {
+                symbol: pair,
+                first_update_id: seq,
+                last_update_id: seq,
+                bids,
+                asks,
+            })])
+        } else if channel_name == "trade" {
+            let trades: Vec<(String, String, String, String, String, String)> =
+                serde_json::from_value(payload).context("Failed to parse Kraken trades")?;
+            trades
+                .into_iter()
+                .map(|(price, volume, time, side, _order_type, _misc)| {
+                    let timestamp_ms = (f64::from_str(&time).unwrap_or(0.0) * 1000.0) as i64;
+                    let trade_id = self.next_trade_id.fetch_add(1, Ordering::Relaxed);
+                    Trade::parse(pair.clone(), trade_id, &price, &volume, timestamp_ms, side == "s")
+                        .map(ExchangeMessage::Trade)
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .context("invalid price/quantity in Kraken trade")
+        } else if channel_name == "ticker" {
+            let ticker: KrakenTickerPayload = serde_json::from_value(payload)
+                .context("Failed to parse Kraken ticker payload")?;
+            let update_id = self.next_ticker_id.fetch_add(1, Ordering::Relaxed);
+            let book_ticker = BookTicker {
+                symbol: pair,
+                update_id,
+                bid_price: Decimal::from_str(&ticker.bid[0]).context("invalid ticker bid price")?,
+                bid_qty: Decimal::from_str(&ticker.bid[2]).context("invalid ticker bid qty")?,
+                ask_price: Decimal::from_str(&ticker.ask[0]).context("invalid ticker ask price")?,
+                ask_qty: Decimal::from_str(&ticker.ask[2]).context("invalid ticker ask qty")?,
+            };
+            Ok(vec![ExchangeMessage::BookTicker(book_ticker)])
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    fn heartbeat_timeout(&self) -> Duration {
+        // Kraken pushes a `heartbeat` event roughly once a second on an
+        // otherwise idle channel; a minute of total silence means the
+        // connection is dead, not just quiet.
+        Duration::from_secs(60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binance_adapter_builds_combined_stream_url() {
+        let adapter = BinanceAdapter;
+        let url = adapter.subscribe_url("wss://fstream.binance.com/stream", "BTCUSDT", "1m");
+        assert_eq!(
+            url,
+            "wss://fstream.binance.com/stream?streams=btcusdt@depth@100ms/btcusdt@aggTrade/btcusdt@kline_1m/btcusdt@bookTicker"
+        );
+    }
+
+    #[test]
+    fn binance_adapter_parses_depth_update() {
+        let adapter = BinanceAdapter;
+        let text = serde_json::json!({
+            "stream": "btcusdt@depth@100ms",
+            "data": {
+                "e": "depthUpdate",
+                "s": "BTCUSDT",
+                "U": 1,
+                "u": 2,
+                "b": [["100.0", "1.5"]],
+                "a": [["101.0", "2.5"]],
+            }
+        })
+        .to_string();
+
+        let messages = adapter.parse_frame(&text).unwrap();
+        assert_eq!(messages.len(), 1);
+        match &messages[0] {
+            ExchangeMessage::Depth(update) => {
+                assert_eq!(update.symbol, "BTCUSDT");
+                assert_eq!(update.bids.len(), 1);
+                assert_eq!(update.asks.len(), 1);
+            }
+            other => panic!("expected Depth, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn binance_adapter_ignores_subscription_ack() {
+        let adapter = BinanceAdapter;
+        let messages = adapter.parse_frame(r#"{"result":null,"id":1}"#).unwrap();
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn kraken_adapter_ignores_control_frames() {
+        let adapter = KrakenAdapter::new();
+        let messages = adapter
+            .parse_frame(r#"{"event":"heartbeat"}"#)
+            .unwrap();
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn kraken_adapter_parses_book_update() {
+        let adapter = KrakenAdapter::new();
+        let text = r#"[336,{"a":[["5541.30000","2.50700000","1534614248.123678"]]},"book-10","XBT/USD"]"#;
+        let messages = adapter.parse_frame(text).unwrap();
+        assert_eq!(messages.len(), 1);
+        match &messages[0] {
+            ExchangeMessage::Depth(update) => {
+                assert_eq!(update.symbol, "XBT/USD");
+                assert_eq!(update.asks.len(), 1);
+                assert_eq!(update.first_update_id, update.last_update_id);
+            }
+            other => panic!("expected Depth, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn kraken_adapter_parses_trades() {
+        let adapter = KrakenAdapter::new();
+        let text = r#"[337,[["5541.20000","0.15850568","1534614057.321597","s","l",""]],"trade","XBT/USD"]"#;
+        let messages = adapter.parse_frame(text).unwrap();
+        assert_eq!(messages.len(), 1);
+        match &messages[0] {
+            ExchangeMessage::Trade(trade) => {
+                assert_eq!(trade.symbol, "XBT/USD");
+                assert!(trade.is_buyer_maker);
+            }
+            other => panic!("expected Trade, got {:?}", other),
+        }
+    }
+}
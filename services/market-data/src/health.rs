@@ -1,30 +1,54 @@
 use axum::{
-    extract::State,
+    extract::{FromRef, Query, State},
     http::{StatusCode, HeaderMap, header::{ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_HEADERS, CONTENT_TYPE}},
     response::{IntoResponse, Response},
     routing::get,
     Json, Router,
 };
+use serde::Deserialize;
 use serde_json::json;
 use std::sync::Arc;
 use tracing::info;
 
+use crate::candles::{CandleAggregator, Resolution};
 use crate::metrics;
+use crate::orderbook::OrderBookManager;
+
+#[derive(Clone, FromRef)]
+struct AppState {
+    orderbook_manager: Arc<OrderBookManager>,
+    candle_aggregator: Arc<CandleAggregator>,
+}
 
 pub struct HealthServer {
     port: u16,
+    orderbook_manager: Arc<OrderBookManager>,
+    candle_aggregator: Arc<CandleAggregator>,
 }
 
 impl HealthServer {
-    pub fn new(port: u16) -> Self {
-        Self { port }
+    pub fn new(
+        port: u16,
+        orderbook_manager: Arc<OrderBookManager>,
+        candle_aggregator: Arc<CandleAggregator>,
+    ) -> Self {
+        Self { port, orderbook_manager, candle_aggregator }
     }
 
     pub async fn start(self) -> Result<(), Box<dyn std::error::Error>> {
+        let state = AppState {
+            orderbook_manager: self.orderbook_manager,
+            candle_aggregator: self.candle_aggregator,
+        };
+
         let app = Router::new()
             .route("/health", get(health_handler))
             .route("/metrics", get(metrics_handler))
-            .route("/ready", get(readiness_handler));
+            .route("/ready", get(readiness_handler))
+            .route("/orderbook", get(orderbook_handler))
+            .route("/ticker", get(ticker_handler))
+            .route("/candles", get(candles_handler))
+            .with_state(state);
 
         let addr = format!("0.0.0.0:{}", self.port);
         info!("Health server listening on {}", addr);
@@ -36,6 +60,129 @@ impl HealthServer {
     }
 }
 
+fn default_depth() -> usize {
+    20
+}
+
+#[derive(Debug, Deserialize)]
+struct SymbolQuery {
+    symbol: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OrderbookQuery {
+    symbol: String,
+    #[serde(default = "default_depth")]
+    depth: usize,
+}
+
+async fn orderbook_handler(
+    State(orderbook_manager): State<Arc<OrderBookManager>>,
+    Query(params): Query<OrderbookQuery>,
+) -> impl IntoResponse {
+    let mut headers = HeaderMap::new();
+    headers = add_cors_headers(headers);
+
+    match orderbook_manager.get(&params.symbol) {
+        Some(book) => {
+            let (bids, asks) = book.get_top_levels(params.depth);
+            (
+                StatusCode::OK,
+                headers,
+                Json(json!({
+                    "symbol": book.symbol,
+                    "last_update_id": book.last_update_id,
+                    "bids": bids,
+                    "asks": asks,
+                })),
+            )
+                .into_response()
+        }
+        None => (
+            StatusCode::NOT_FOUND,
+            headers,
+            Json(json!({ "error": format!("unknown symbol {}", params.symbol) })),
+        )
+            .into_response(),
+    }
+}
+
+async fn ticker_handler(
+    State(orderbook_manager): State<Arc<OrderBookManager>>,
+    Query(params): Query<SymbolQuery>,
+) -> impl IntoResponse {
+    let mut headers = HeaderMap::new();
+    headers = add_cors_headers(headers);
+
+    match orderbook_manager.get(&params.symbol) {
+        Some(book) => (
+            StatusCode::OK,
+            headers,
+            Json(json!({
+                "symbol": book.symbol,
+                "best_bid": book.best_bid(),
+                "best_ask": book.best_ask(),
+                "mid_price": book.mid_price(),
+                "micro_price": book.micro_price(),
+                "spread": book.spread(),
+                "spread_bps": book.spread_bps(),
+                "imbalance": book.imbalance(10),
+                "last_update_id": book.last_update_id,
+            })),
+        )
+            .into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            headers,
+            Json(json!({ "error": format!("unknown symbol {}", params.symbol) })),
+        )
+            .into_response(),
+    }
+}
+
+fn default_to() -> i64 {
+    i64::MAX
+}
+
+#[derive(Debug, Deserialize)]
+struct CandlesQuery {
+    symbol: String,
+    resolution: String,
+    #[serde(default)]
+    from: i64,
+    #[serde(default = "default_to")]
+    to: i64,
+}
+
+async fn candles_handler(
+    State(candle_aggregator): State<Arc<CandleAggregator>>,
+    Query(params): Query<CandlesQuery>,
+) -> impl IntoResponse {
+    let mut headers = HeaderMap::new();
+    headers = add_cors_headers(headers);
+
+    let Some(resolution) = Resolution::parse(&params.resolution) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            headers,
+            Json(json!({ "error": format!("unknown resolution {}", params.resolution) })),
+        )
+            .into_response();
+    };
+
+    let candles = candle_aggregator.candles(&params.symbol, resolution, params.from, params.to);
+    (
+        StatusCode::OK,
+        headers,
+        Json(json!({
+            "symbol": params.symbol,
+            "resolution": resolution.as_str(),
+            "candles": candles,
+        })),
+    )
+        .into_response()
+}
+
 // Helper function to add CORS headers
 fn add_cors_headers(mut headers: HeaderMap) -> HeaderMap {
     headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, "*".parse().unwrap());
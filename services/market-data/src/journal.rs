@@ -0,0 +1,298 @@
+//! Append-only on-disk journal for `DepthUpdate`/`Trade` records, so the
+//! service can restart and replay, and historical backfills can be served
+//! without a database.
+//!
+//! Two files make up a journal, modeled on a classic data+index log:
+//!
+//! - `data.log`: length-prefixed serialized records, back to back
+//!   (`[u32 length][payload]`, repeated).
+//! - `data.idx`: one fixed-size entry per record (`u64 offset`, `u64
+//!   length`), so any record can be seeked to in O(1) by its sequence
+//!   number (its position in the index).
+//!
+//! On open, the index is validated against the data file's length and any
+//! torn trailing write (a partial index entry, or a data record whose
+//! prefix promises more bytes than the file actually has) is truncated
+//! away, so a crash mid-append never corrupts earlier records.
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::orderbook::{DepthUpdate, OrderBookManager, Trade};
+
+const INDEX_ENTRY_SIZE: u64 = 16; // offset: u64, length: u64
+const LENGTH_PREFIX_SIZE: u64 = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum JournalRecord {
+    Depth(DepthUpdate),
+    Trade(Trade),
+}
+
+pub struct Journal {
+    data_file: File,
+    index_file: File,
+    next_offset: u64,
+}
+
+impl Journal {
+    /// Opens (creating if needed) the `data.log`/`data.idx` pair under
+    /// `dir`, recovering from any torn trailing write left by a prior crash.
+    pub fn open(dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(dir).context("Failed to create journal directory")?;
+
+        let mut data_file = open_rw(&data_path(dir))?;
+        let mut index_file = open_rw(&index_path(dir))?;
+
+        let next_offset = recover(&mut data_file, &mut index_file)?;
+
+        Ok(Self { data_file, index_file, next_offset })
+    }
+
+    /// Appends `record`, returning its sequence number (its position in the
+    /// index, 0-based).
+    pub fn append(&mut self, record: &JournalRecord) -> Result<u64> {
+        let payload = serde_json::to_vec(record).context("Failed to serialize journal record")?;
+        let offset = self.next_offset;
+
+        self.data_file.seek(SeekFrom::Start(offset))?;
+        self.data_file.write_all(&(payload.len() as u32).to_le_bytes())?;
+        self.data_file.write_all(&payload)?;
+        self.data_file.flush()?;
+
+        let seq = self.len()?;
+        self.index_file.seek(SeekFrom::End(0))?;
+        self.index_file.write_all(&offset.to_le_bytes())?;
+        self.index_file.write_all(&(payload.len() as u64).to_le_bytes())?;
+        self.index_file.flush()?;
+
+        self.next_offset = offset + LENGTH_PREFIX_SIZE + payload.len() as u64;
+        Ok(seq)
+    }
+
+    /// Number of records currently in the journal.
+    pub fn len(&self) -> Result<u64> {
+        Ok(self.index_file.metadata()?.len() / INDEX_ENTRY_SIZE)
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Reads back the record at sequence number `seq`.
+    pub fn read_at(&mut self, seq: u64) -> Result<JournalRecord> {
+        let (offset, length) = self.index_entry(seq)?;
+
+        self.data_file.seek(SeekFrom::Start(offset + LENGTH_PREFIX_SIZE))?;
+        let mut payload = vec![0u8; length as usize];
+        self.data_file.read_exact(&mut payload)?;
+
+        serde_json::from_slice(&payload).context("Failed to deserialize journal record")
+    }
+
+    /// Streams the records in `[from_seq, to_seq]`, oldest first.
+    pub fn replay_range(&mut self, from_seq: u64, to_seq: u64) -> Result<Vec<JournalRecord>> {
+        let len = self.len()?;
+        let to_seq = to_seq.min(len.saturating_sub(1));
+
+        let mut records = Vec::new();
+        let mut seq = from_seq;
+        while seq <= to_seq {
+            records.push(self.read_at(seq)?);
+            seq += 1;
+        }
+        Ok(records)
+    }
+
+    /// Replays `[from_seq, to_seq]` directly into `manager`, rebuilding book
+    /// state at an arbitrary point without a live exchange connection.
+    pub fn rebuild_orderbook(
+        &mut self,
+        manager: &OrderBookManager,
+        from_seq: u64,
+        to_seq: u64,
+    ) -> Result<()> {
+        for record in self.replay_range(from_seq, to_seq)? {
+            if let JournalRecord::Depth(update) = record {
+                let symbol = update.symbol.clone();
+                manager.update(&symbol, update);
+            }
+        }
+        Ok(())
+    }
+
+    fn index_entry(&mut self, seq: u64) -> Result<(u64, u64)> {
+        self.index_file.seek(SeekFrom::Start(seq * INDEX_ENTRY_SIZE))?;
+        let mut buf = [0u8; INDEX_ENTRY_SIZE as usize];
+        self.index_file
+            .read_exact(&mut buf)
+            .with_context(|| format!("No journal entry at sequence {}", seq))?;
+
+        let offset = u64::from_le_bytes(buf[0..8].try_into().unwrap());
+        let length = u64::from_le_bytes(buf[8..16].try_into().unwrap());
+        Ok((offset, length))
+    }
+}
+
+fn data_path(dir: &Path) -> PathBuf {
+    dir.join("data.log")
+}
+
+fn index_path(dir: &Path) -> PathBuf {
+    dir.join("data.idx")
+}
+
+fn open_rw(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .read(true)
+        .write(true)
+        .open(path)
+        .with_context(|| format!("Failed to open journal file {}", path.display()))
+}
+
+/// Truncates a torn trailing write left by a crash mid-append: a partial
+/// index entry, or an index entry whose record promises more bytes than the
+/// data file actually holds. Returns the data file offset the next append
+/// should start at.
+fn recover(data_file: &mut File, index_file: &mut File) -> Result<u64> {
+    let index_len = index_file.metadata()?.len();
+    let whole_entries = index_len / INDEX_ENTRY_SIZE;
+    if index_len % INDEX_ENTRY_SIZE != 0 {
+        index_file.set_len(whole_entries * INDEX_ENTRY_SIZE)?;
+    }
+
+    let data_len = data_file.metadata()?.len();
+    if whole_entries == 0 {
+        data_file.set_len(0)?;
+        return Ok(0);
+    }
+
+    index_file.seek(SeekFrom::Start((whole_entries - 1) * INDEX_ENTRY_SIZE))?;
+    let mut buf = [0u8; INDEX_ENTRY_SIZE as usize];
+    index_file.read_exact(&mut buf)?;
+    let offset = u64::from_le_bytes(buf[0..8].try_into().unwrap());
+    let length = u64::from_le_bytes(buf[8..16].try_into().unwrap());
+    let record_end = offset + LENGTH_PREFIX_SIZE + length;
+
+    if record_end > data_len {
+        // The last record's bytes were never fully flushed; drop its index
+        // entry too and roll back to the record before it.
+        index_file.set_len((whole_entries - 1) * INDEX_ENTRY_SIZE)?;
+        data_file.set_len(offset)?;
+        return Ok(offset);
+    }
+
+    data_file.set_len(record_end)?;
+    Ok(record_end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::PriceLevel;
+
+    fn trade(trade_id: u64) -> JournalRecord {
+        JournalRecord::Trade(Trade::new("BTCUSDT", trade_id, 100.0, 1.0, 0, false))
+    }
+
+    #[test]
+    fn test_append_and_read_at_round_trip() {
+        let dir = tempdir();
+        let mut journal = Journal::open(&dir).unwrap();
+
+        let seq0 = journal.append(&trade(1)).unwrap();
+        let seq1 = journal.append(&trade(2)).unwrap();
+        assert_eq!((seq0, seq1), (0, 1));
+
+        match journal.read_at(1).unwrap() {
+            JournalRecord::Trade(t) => assert_eq!(t.trade_id, 2),
+            _ => panic!("expected a trade record"),
+        }
+    }
+
+    #[test]
+    fn test_replay_range_streams_records_in_order() {
+        let dir = tempdir();
+        let mut journal = Journal::open(&dir).unwrap();
+
+        for id in 0..5 {
+            journal.append(&trade(id)).unwrap();
+        }
+
+        let records = journal.replay_range(1, 3).unwrap();
+        assert_eq!(records.len(), 3);
+        for (i, record) in records.iter().enumerate() {
+            match record {
+                JournalRecord::Trade(t) => assert_eq!(t.trade_id, i as u64 + 1),
+                _ => panic!("expected a trade record"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_recovery_truncates_torn_trailing_write() {
+        let dir = tempdir();
+        {
+            let mut journal = Journal::open(&dir).unwrap();
+            journal.append(&trade(1)).unwrap();
+            journal.append(&trade(2)).unwrap();
+        }
+
+        // Simulate a crash mid-append: the index entry was written but the
+        // data bytes it points at were never fully flushed.
+        {
+            let mut index_file = OpenOptions::new().append(true).open(index_path(&dir)).unwrap();
+            let torn_offset = std::fs::metadata(data_path(&dir)).unwrap().len();
+            index_file.write_all(&torn_offset.to_le_bytes()).unwrap();
+            index_file.write_all(&999u64.to_le_bytes()).unwrap();
+        }
+
+        let mut journal = Journal::open(&dir).unwrap();
+        assert_eq!(journal.len().unwrap(), 2);
+        match journal.read_at(1).unwrap() {
+            JournalRecord::Trade(t) => assert_eq!(t.trade_id, 2),
+            _ => panic!("expected a trade record"),
+        }
+
+        // The journal is still writable after recovery.
+        let seq = journal.append(&trade(3)).unwrap();
+        assert_eq!(seq, 2);
+    }
+
+    #[test]
+    fn test_rebuild_orderbook_replays_depth_updates() {
+        let dir = tempdir();
+        let mut journal = Journal::open(&dir).unwrap();
+
+        journal
+            .append(&JournalRecord::Depth(DepthUpdate {
+                symbol: "BTCUSDT".to_string(),
+                first_update_id: 1,
+                last_update_id: 1,
+                bids: vec![PriceLevel::new(100.0, 1.0)],
+                asks: vec![],
+            }))
+            .unwrap();
+
+        let manager = OrderBookManager::new(10);
+        journal.rebuild_orderbook(&manager, 0, 0).unwrap();
+
+        let book = manager.get("BTCUSDT").unwrap();
+        assert_eq!(book.last_update_id, 1);
+    }
+
+    fn tempdir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "market-data-journal-test-{}-{:?}",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+}
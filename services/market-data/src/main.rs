@@ -1,10 +1,23 @@
+mod candles;
 mod config;
+mod depth_sync;
+mod exchange;
 mod orderbook;
 mod publisher;
+mod snapshot;
 mod websocket;
 mod metrics;
 mod shm;
 mod health;
+mod ws_server;
+mod wire;
+mod journal;
+mod backtest;
+mod user_stream;
+mod multi_stream;
+mod ticker;
+mod sinks;
+mod storage;
 
 use anyhow::Result;
 use std::sync::Arc;
@@ -12,11 +25,16 @@ use tokio::signal;
 use tracing::{info, error};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+use crate::candles::CandleAggregator;
 use crate::config::Config;
 use crate::orderbook::OrderBookManager;
 use crate::publisher::Publisher;
+use crate::snapshot;
 use crate::websocket::WebSocketClient;
 use crate::health::HealthServer;
+use crate::ws_server::DashboardServer;
+use crate::sinks::{MarketDataSink, NatsSink};
+use crate::storage::StorageWriter;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -43,56 +61,157 @@ async fn main() -> Result<()> {
 
     // Initialize shared components
     let orderbook_manager = Arc::new(OrderBookManager::new(config.order_book_depth));
+    let candle_aggregator = Arc::new(CandleAggregator::new(config.candle_history_size));
+
+    // Additional sinks to mirror order books/trades to, alongside the
+    // always-on Redis/shm path `Publisher` owns directly.
+    let mut sinks: Vec<Arc<dyn MarketDataSink>> = Vec::new();
+    if config.nats_enabled {
+        match NatsSink::connect(&config.nats_url, &config.nats_stream_name).await {
+            Ok(sink) => sinks.push(Arc::new(sink)),
+            Err(e) => error!("Failed to connect NATS sink, continuing without it: {}", e),
+        }
+    }
+
+    // Optional batched persistence of trades/finalized candles into
+    // Postgres/TimescaleDB, for historical range queries and candle
+    // backfill-on-restart.
+    let storage = if config.storage_enabled {
+        match StorageWriter::connect(&config.database_url, config.storage_batch_size).await {
+            Ok(writer) => Some(writer),
+            Err(e) => {
+                error!("Failed to connect storage writer, continuing without it: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     let publisher = Arc::new(
-        Publisher::new(&config.redis_url, &config.shm_name)
+        Publisher::new(
+            &config.redis_url,
+            &config.shm_name,
+            Arc::clone(&candle_aggregator),
+            config.ticker_window_secs,
+            sinks,
+            storage,
+        )
             .await
             .expect("Failed to initialize publisher")
     );
 
     // Start health check server
-    let health_server = HealthServer::new(config.health_port);
+    let health_server = HealthServer::new(
+        config.health_port,
+        Arc::clone(&orderbook_manager),
+        Arc::clone(&candle_aggregator),
+    );
     let health_handle = tokio::spawn(async move {
         if let Err(e) = health_server.start().await {
             error!("Health server error: {}", e);
         }
     });
 
-    // Start WebSocket clients for each symbol
-    let mut ws_handles = Vec::new();
+    // Start the dashboard WebSocket fan-out server
+    let dashboard_server = Arc::new(DashboardServer::new(
+        config.dashboard_ws_port,
+        Arc::clone(&orderbook_manager),
+    ));
+    let dashboard_handle = {
+        let dashboard_server = Arc::clone(&dashboard_server);
+        tokio::spawn(async move {
+            if let Err(e) = dashboard_server.run().await {
+                error!("Dashboard WebSocket server error: {}", e);
+            }
+        })
+    };
 
-    for symbol in &config.symbols {
-        let symbol = symbol.clone();
-        let ws_url = config.exchange_ws_url.clone();
-        let orderbook_manager = Arc::clone(&orderbook_manager);
-        let publisher = Arc::clone(&publisher);
+    // Start WebSocket clients: either one combined-stream connection for all
+    // symbols, or one connection per symbol, depending on configuration.
+    let mut ws_handles = Vec::new();
 
+    if config.multi_stream_enabled {
+        let snapshot_fetcher = snapshot::source_for(&config.exchange, config.exchange_rest_url.clone())
+            .expect("Failed to build snapshot source");
+        let multi_stream_client = Arc::new(multi_stream::MultiStreamClient::new(
+            config.exchange_ws_url.clone(),
+            config.symbols.clone(),
+            Arc::clone(&orderbook_manager),
+            Arc::clone(&publisher),
+            snapshot_fetcher,
+            Arc::clone(&dashboard_server),
+        ));
         let handle = tokio::spawn(async move {
-            let client = WebSocketClient::new(
-                symbol.clone(),
-                ws_url,
-                orderbook_manager,
-                publisher,
-            );
-
-            loop {
-                info!("Starting WebSocket client for {}", symbol);
-                match client.connect_and_run().await {
-                    Ok(_) => {
-                        info!("WebSocket client for {} exited normally", symbol);
-                        break;
-                    }
-                    Err(e) => {
-                        error!("WebSocket client error for {}: {}", symbol, e);
-                        // Exponential backoff
-                        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-                    }
-                }
+            if let Err(e) = multi_stream_client.connect_and_run().await {
+                error!("Multi-stream client error: {}", e);
             }
         });
-
         ws_handles.push(handle);
+    } else {
+        for symbol in &config.symbols {
+            let symbol = symbol.clone();
+            let ws_url = config.exchange_ws_url.clone();
+            let orderbook_manager = Arc::clone(&orderbook_manager);
+            let publisher = Arc::clone(&publisher);
+            let snapshot_fetcher = snapshot::source_for(&config.exchange, config.exchange_rest_url.clone())
+                .expect("Failed to build snapshot source");
+            let order_book_depth = config.order_book_depth;
+            let dashboard_server = Arc::clone(&dashboard_server);
+            let kline_interval = config.kline_interval.clone();
+            let adapter = exchange::adapter_for(&config.exchange)
+                .expect("Failed to build exchange adapter");
+
+            let handle = tokio::spawn(async move {
+                let client = WebSocketClient::new(
+                    symbol.clone(),
+                    ws_url,
+                    orderbook_manager,
+                    publisher,
+                    snapshot_fetcher,
+                    order_book_depth,
+                    dashboard_server,
+                    kline_interval,
+                    adapter,
+                );
+
+                loop {
+                    info!("Starting WebSocket client for {}", symbol);
+                    match client.connect_and_run().await {
+                        Ok(_) => {
+                            info!("WebSocket client for {} exited normally", symbol);
+                            break;
+                        }
+                        Err(e) => {
+                            error!("WebSocket client error for {}: {}", symbol, e);
+                            // Fixed delay before restarting the client
+                            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                        }
+                    }
+                }
+            });
+
+            ws_handles.push(handle);
+        }
     }
 
+    // Start the authenticated user-data stream (account/order events), if configured.
+    let user_data_handle = if config.user_data_enabled {
+        let user_data_client = user_stream::UserDataClient::new(
+            config.exchange_rest_url.clone(),
+            config.exchange_ws_url.clone(),
+            config.binance_api_key.clone(),
+            Arc::clone(&publisher),
+        );
+        Some(tokio::spawn(async move {
+            if let Err(e) = user_data_client.connect_and_run().await {
+                error!("User data stream error: {}", e);
+            }
+        }))
+    } else {
+        None
+    };
+
     info!("All WebSocket clients started");
 
     // Wait for shutdown signal
@@ -110,6 +229,10 @@ async fn main() -> Result<()> {
         handle.abort();
     }
     health_handle.abort();
+    dashboard_handle.abort();
+    if let Some(handle) = user_data_handle {
+        handle.abort();
+    }
 
     info!("Market Data Service stopped");
     Ok(())
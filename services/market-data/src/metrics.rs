@@ -58,6 +58,16 @@ lazy_static! {
     )
     .unwrap();
 
+    // Distinct from `SEQUENCE_ERRORS`: counts full REST-snapshot resyncs
+    // (`DepthSynchronizer::resync`), which is a slower, more disruptive
+    // recovery than a single sequence error and worth alerting on separately.
+    pub static ref RESYNCS_TOTAL: CounterVec = register_counter_vec!(
+        "depth_resyncs_total",
+        "Total number of full REST-snapshot depth resynchronizations",
+        &["symbol"]
+    )
+    .unwrap();
+
     // Trades
     pub static ref TRADES_PROCESSED: CounterVec = register_counter_vec!(
         "trades_processed_total",
@@ -66,6 +76,22 @@ lazy_static! {
     )
     .unwrap();
 
+    // Klines
+    pub static ref KLINES_PROCESSED: CounterVec = register_counter_vec!(
+        "klines_processed_total",
+        "Total number of kline/candlestick updates processed",
+        &["symbol", "interval"]
+    )
+    .unwrap();
+
+    // Book ticker
+    pub static ref BOOK_TICKER_UPDATES: CounterVec = register_counter_vec!(
+        "book_ticker_updates_total",
+        "Total number of best-bid/ask book ticker updates processed",
+        &["symbol"]
+    )
+    .unwrap();
+
     // Redis publishing
     pub static ref REDIS_PUBLISHES: CounterVec = register_counter_vec!(
         "redis_publishes_total",
@@ -80,6 +106,46 @@ lazy_static! {
         &["symbol"]
     )
     .unwrap();
+
+    /// Messages evicted from the Publisher's outbox (oldest-first) because
+    /// it filled up while Redis was unreachable.
+    pub static ref REDIS_DROPPED: CounterVec = register_counter_vec!(
+        "redis_dropped_total",
+        "Total number of outbound Redis messages dropped from the reconnect buffer",
+        &["kind"]
+    )
+    .unwrap();
+
+    // Pluggable `sinks::MarketDataSink` backends (e.g. NATS), generic across
+    // whatever backends are configured, unlike the Redis-specific counters above.
+    pub static ref SINK_PUBLISHES: CounterVec = register_counter_vec!(
+        "sink_publishes_total",
+        "Total number of publishes to a pluggable market data sink",
+        &["backend", "type"]
+    )
+    .unwrap();
+
+    pub static ref SINK_ERRORS: CounterVec = register_counter_vec!(
+        "sink_errors_total",
+        "Total number of publish errors from a pluggable market data sink",
+        &["backend", "type"]
+    )
+    .unwrap();
+
+    // TimescaleDB/Postgres persistence (see `storage::StorageWriter`).
+    pub static ref DB_WRITES: CounterVec = register_counter_vec!(
+        "db_writes_total",
+        "Total number of rows batch-inserted into the storage database",
+        &["type"]
+    )
+    .unwrap();
+
+    pub static ref DB_ERRORS: CounterVec = register_counter_vec!(
+        "db_errors_total",
+        "Total number of storage database write failures or dropped events",
+        &["type"]
+    )
+    .unwrap();
 }
 
 pub fn encode_metrics() -> Result<String, Box<dyn std::error::Error>> {
@@ -0,0 +1,419 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::sleep;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, info, warn};
+
+use crate::depth_sync::{BookUpdate, DepthSynchronizer};
+use crate::orderbook::{DepthUpdate, OrderBookManager, PriceLevel, Trade};
+use crate::publisher::Publisher;
+use crate::snapshot::SnapshotSource;
+use crate::ws_server::DashboardServer;
+use crate::metrics;
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "e")]
+enum BinanceMessage {
+    #[serde(rename = "depthUpdate")]
+    DepthUpdate(BinanceDepthUpdate),
+    #[serde(rename = "aggTrade")]
+    AggTrade(BinanceAggTrade),
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceDepthUpdate {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "U")]
+    first_update_id: u64,
+    #[serde(rename = "u")]
+    last_update_id: u64,
+    #[serde(rename = "b")]
+    bids: Vec<(String, String)>,
+    #[serde(rename = "a")]
+    asks: Vec<(String, String)>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceAggTrade {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "a")]
+    trade_id: u64,
+    #[serde(rename = "p")]
+    price: String,
+    #[serde(rename = "q")]
+    quantity: String,
+    #[serde(rename = "T")]
+    timestamp: i64,
+    #[serde(rename = "m")]
+    is_buyer_maker: bool,
+}
+
+#[derive(Serialize)]
+struct StreamControl<'a> {
+    method: &'a str,
+    params: Vec<String>,
+    id: u64,
+}
+
+/// A single combined-stream WebSocket connection carrying `@depth@100ms` and
+/// `@aggTrade` for many symbols at once, instead of one socket per symbol.
+/// Each symbol still gets its own [`DepthSynchronizer`] so the REST-snapshot
+/// bootstrap/resync procedure is unchanged - only the transport is shared.
+/// Symbols can be added or removed at runtime via `subscribe`/`unsubscribe`,
+/// which send Binance's `SUBSCRIBE`/`UNSUBSCRIBE` control frames over the
+/// write half, each with a fresh request `id`. Acks are not matched against
+/// those ids; they are only logged at debug level.
+pub struct MultiStreamClient {
+    ws_url: String,
+    orderbook_manager: Arc<OrderBookManager>,
+    publisher: Arc<Publisher>,
+    snapshot_fetcher: Arc<dyn SnapshotSource>,
+    dashboard_server: Arc<DashboardServer>,
+    reconnect_delay: Duration,
+    max_reconnect_delay: Duration,
+    next_request_id: AtomicU64,
+    symbols: Mutex<Vec<String>>,
+    control_tx: Mutex<Option<mpsc::Sender<Message>>>,
+}
+
+/// Per-symbol plumbing into a running [`DepthSynchronizer`] task: the depth
+/// diff sender feeding it and the join handles, which are aborted when the
+/// connection loop exits.
+struct SymbolHandle {
+    depth_tx: mpsc::Sender<DepthUpdate>,
+    tasks: Vec<tokio::task::JoinHandle<()>>,
+}
+
+impl MultiStreamClient {
+    pub fn new(
+        ws_url: String,
+        symbols: Vec<String>,
+        orderbook_manager: Arc<OrderBookManager>,
+        publisher: Arc<Publisher>,
+        snapshot_fetcher: Arc<dyn SnapshotSource>,
+        dashboard_server: Arc<DashboardServer>,
+    ) -> Self {
+        Self {
+            ws_url,
+            orderbook_manager,
+            publisher,
+            snapshot_fetcher,
+            dashboard_server,
+            reconnect_delay: Duration::from_millis(1000),
+            max_reconnect_delay: Duration::from_secs(60),
+            next_request_id: AtomicU64::new(1),
+            symbols: Mutex::new(symbols),
+            control_tx: Mutex::new(None),
+        }
+    }
+
+    /// Requests that `symbol` be added to the live connection's stream set.
+    /// Takes effect immediately if a connection is up; otherwise it is
+    /// picked up on the next (re)connect.
+    pub async fn subscribe(&self, symbol: &str) -> Result<()> {
+        {
+            let mut symbols = self.symbols.lock().await;
+            if !symbols.iter().any(|s| s.eq_ignore_ascii_case(symbol)) {
+                symbols.push(symbol.to_string());
+            }
+        }
+        self.send_control("SUBSCRIBE", symbol).await
+    }
+
+    /// Requests that `symbol` be dropped from the live connection's stream
+    /// set. Takes effect immediately if a connection is up; otherwise it is
+    /// picked up on the next (re)connect. On a live connection the symbol's
+    /// depth synchronizer is not stopped; it stops receiving diffs and is
+    /// aborted when the connection loop exits.
+    pub async fn unsubscribe(&self, symbol: &str) -> Result<()> {
+        {
+            let mut symbols = self.symbols.lock().await;
+            symbols.retain(|s| !s.eq_ignore_ascii_case(symbol));
+        }
+        self.send_control("UNSUBSCRIBE", symbol).await
+    }
+
+    async fn send_control(&self, method: &str, symbol: &str) -> Result<()> {
+        let Some(tx) = self.control_tx.lock().await.clone() else {
+            // No live connection yet; the (re)connect picks up `self.symbols`.
+            return Ok(());
+        };
+
+        let id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        let lower = symbol.to_lowercase();
+        let control = StreamControl {
+            method,
+            params: vec![format!("{}@depth@100ms", lower), format!("{}@aggTrade", lower)],
+            id,
+        };
+        let payload = serde_json::to_string(&control).context("Failed to serialize control frame")?;
+        tx.send(Message::Text(payload))
+            .await
+            .context("control channel closed")?;
+        Ok(())
+    }
+
+    pub async fn connect_and_run(&self) -> Result<()> {
+        let mut current_delay = self.reconnect_delay;
+
+        loop {
+            match self.run_connection().await {
+                Ok(_) => {
+                    info!("Multi-stream WebSocket connection closed normally");
+                    return Ok(());
+                }
+                Err(e) => {
+                    warn!("Multi-stream WebSocket error: {}", e);
+                    metrics::WS_DISCONNECTS.with_label_values(&["multi"]).inc();
+                    warn!("Reconnecting multi-stream client in {:?}...", current_delay);
+                    sleep(current_delay).await;
+                    current_delay = std::cmp::min(current_delay * 2, self.max_reconnect_delay);
+                }
+            }
+        }
+    }
+
+    async fn run_connection(&self) -> Result<()> {
+        let symbols = self.symbols.lock().await.clone();
+        let streams = symbols
+            .iter()
+            .flat_map(|s| {
+                let lower = s.to_lowercase();
+                vec![format!("{}@depth@100ms", lower), format!("{}@aggTrade", lower)]
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        let url = format!("{}?streams={}", self.ws_url, streams);
+
+        info!("Connecting multi-stream client to {} ({} symbols)", url, symbols.len());
+        let (ws_stream, _) = connect_async(&url)
+            .await
+            .context("Failed to connect to multi-stream WebSocket")?;
+        metrics::WS_CONNECTED.with_label_values(&["multi"]).set(1.0);
+
+        let (mut write, mut read) = ws_stream.split();
+
+        // Outbound control/ping frames share one channel so only this task
+        // owns `write`; `subscribe`/`unsubscribe` send through it.
+        let (control_tx, mut control_rx) = mpsc::channel::<Message>(32);
+        *self.control_tx.lock().await = Some(control_tx.clone());
+
+        tokio::spawn(async move {
+            let mut ping_interval = tokio::time::interval(Duration::from_secs(30));
+            loop {
+                tokio::select! {
+                    _ = ping_interval.tick() => {
+                        if write.send(Message::Ping(vec![])).await.is_err() {
+                            break;
+                        }
+                    }
+                    Some(msg) = control_rx.recv() => {
+                        if write.send(msg).await.is_err() {
+                            break;
+                        }
+                    }
+                    else => break,
+                }
+            }
+        });
+
+        let (update_tx, mut update_rx) = mpsc::channel::<(String, BookUpdate)>(64);
+
+        let mut handles: HashMap<String, SymbolHandle> = HashMap::new();
+        for symbol in &symbols {
+            self.spawn_symbol_sync(symbol.clone(), &mut handles, &update_tx);
+        }
+
+        loop {
+            tokio::select! {
+                msg = read.next() => {
+                    let Some(msg) = msg else { break };
+                    let msg = msg.context("Multi-stream WebSocket message error")?;
+
+                    match msg {
+                        Message::Text(text) => {
+                            if let Err(e) = self
+                                .process_message(&text, &mut handles, &update_tx)
+                                .await
+                            {
+                                debug!("Error processing multi-stream message: {}", e);
+                                metrics::MESSAGES_ERROR.with_label_values(&["multi"]).inc();
+                            }
+                        }
+                        Message::Close(_) => {
+                            info!("Multi-stream WebSocket closed");
+                            metrics::WS_CONNECTED.with_label_values(&["multi"]).set(0.0);
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                Some((symbol, update)) = update_rx.recv() => {
+                    let (book, resynced) = match update {
+                        BookUpdate::Applied(book) => (book, false),
+                        BookUpdate::Resynced(book) => (book, true),
+                    };
+
+                    if !book.bids.is_empty() && !book.asks.is_empty() {
+                        metrics::ORDERBOOK_UPDATES.with_label_values(&[&symbol]).inc();
+
+                        let previous = self.orderbook_manager.get(&symbol);
+                        self.orderbook_manager.set(&symbol, book.clone());
+                        if resynced {
+                            self.dashboard_server.broadcast_checkpoint(&symbol, &book).await;
+                        } else {
+                            self.dashboard_server
+                                .broadcast_update(&symbol, previous.as_ref(), &book)
+                                .await;
+                        }
+
+                        self.publisher
+                            .publish_orderbook(&book)
+                            .await
+                            .context("Failed to publish orderbook")?;
+                    }
+                }
+            }
+        }
+
+        for (_, handle) in handles {
+            for task in handle.tasks {
+                task.abort();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn spawn_symbol_sync(
+        &self,
+        symbol: String,
+        handles: &mut HashMap<String, SymbolHandle>,
+        update_tx: &mpsc::Sender<(String, BookUpdate)>,
+    ) {
+        let (depth_tx, depth_rx) = mpsc::channel::<DepthUpdate>(256);
+        let (book_tx, mut book_rx) = mpsc::channel::<BookUpdate>(16);
+
+        let synchronizer = DepthSynchronizer::new(symbol.clone(), Arc::clone(&self.snapshot_fetcher));
+        let sync_symbol = symbol.clone();
+        let sync_task = tokio::spawn(async move {
+            if let Err(e) = synchronizer.run(depth_rx, book_tx).await {
+                warn!("Depth synchronizer for {} exited: {}", sync_symbol, e);
+            }
+        });
+
+        // The synchronizer doesn't know it's sharing a socket with other
+        // symbols, so tag each book with its symbol before forwarding it
+        // onto the connection-wide update channel.
+        let forward_symbol = symbol.clone();
+        let forward_update_tx = update_tx.clone();
+        let forward_task = tokio::spawn(async move {
+            while let Some(book) = book_rx.recv().await {
+                if forward_update_tx
+                    .send((forward_symbol.clone(), book))
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        handles.insert(
+            symbol,
+            SymbolHandle {
+                depth_tx,
+                tasks: vec![sync_task, forward_task],
+            },
+        );
+    }
+
+    async fn process_message(
+        &self,
+        text: &str,
+        handles: &mut HashMap<String, SymbolHandle>,
+        update_tx: &mpsc::Sender<(String, BookUpdate)>,
+    ) -> Result<()> {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            stream: String,
+            data: serde_json::Value,
+        }
+
+        // Control-frame acks (`{"result":null,"id":n}`) have no `stream`
+        // field; anything else is routed by it to the right symbol.
+        if let Ok(wrapper) = serde_json::from_str::<Wrapper>(text) {
+            let symbol = wrapper
+                .stream
+                .split('@')
+                .next()
+                .unwrap_or_default()
+                .to_uppercase();
+
+            if wrapper.stream.contains("depth") {
+                let update: BinanceDepthUpdate = serde_json::from_value(wrapper.data)
+                    .context("Failed to parse depth update")?;
+                // A symbol added by `subscribe` on a live connection gets its
+                // synchronizer on its first diff.
+                if !handles.contains_key(&symbol)
+                    && self.symbols.lock().await.iter().any(|s| s.eq_ignore_ascii_case(&symbol))
+                {
+                    self.spawn_symbol_sync(symbol.clone(), handles, update_tx);
+                }
+                if let Some(handle) = handles.get(&symbol) {
+                    // A malformed price or quantity surfaces as an `Err`
+                    // here rather than silently becoming a phantom zero
+                    // level that can wipe a price level out of the book.
+                    let depth_update = DepthUpdate {
+                        symbol: update.symbol.clone(),
+                        first_update_id: update.first_update_id,
+                        last_update_id: update.last_update_id,
+                        bids: update
+                            .bids
+                            .iter()
+                            .map(|(p, q)| PriceLevel::parse(p, q))
+                            .collect::<Result<Vec<_>, _>>()
+                            .context("invalid bid price/quantity in depth update")?,
+                        asks: update
+                            .asks
+                            .iter()
+                            .map(|(p, q)| PriceLevel::parse(p, q))
+                            .collect::<Result<Vec<_>, _>>()
+                            .context("invalid ask price/quantity in depth update")?,
+                    };
+                    let _ = handle.depth_tx.send(depth_update).await;
+                }
+            } else if wrapper.stream.contains("aggTrade") {
+                let trade_data: BinanceAggTrade = serde_json::from_value(wrapper.data)
+                    .context("Failed to parse trade")?;
+                let trade = Trade::parse(
+                    trade_data.symbol.clone(),
+                    trade_data.trade_id,
+                    &trade_data.price,
+                    &trade_data.quantity,
+                    trade_data.timestamp,
+                    trade_data.is_buyer_maker,
+                )
+                .context("invalid price/quantity in trade")?;
+                metrics::TRADES_PROCESSED.with_label_values(&[&trade.symbol]).inc();
+                self.publisher
+                    .publish_trade(&trade)
+                    .await
+                    .context("Failed to publish trade")?;
+            }
+        } else {
+            debug!("Non-stream frame on multi-stream connection: {}", text);
+        }
+
+        Ok(())
+    }
+}
@@ -1,13 +1,45 @@
 use ahash::AHashMap;
+use rust_decimal::prelude::*;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::BTreeMap;
+use std::str::FromStr;
 use std::sync::RwLock;
 use chrono::Utc;
 
+use crate::wire::Side;
+
+/// A price/quantity pair carrying full exchange precision end-to-end, from
+/// parsing out of the wire format through to `OrderBook::apply_update`.
+/// `f64` is only derived from this at display/hot-path boundaries (the
+/// in-memory book, the shm codec) - see `apply_update` and `wire::encode_level_into`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceLevel {
-    pub price: f64,
-    pub quantity: f64,
+    pub price: Decimal,
+    pub quantity: Decimal,
+}
+
+impl PriceLevel {
+    /// Convenience constructor for call sites that already have `f64`
+    /// values (tests, synthetic books). Real exchange ticks should be
+    /// parsed directly via `Decimal::from_str` so a malformed value
+    /// surfaces as an error instead of silently becoming zero.
+    pub fn new(price: f64, quantity: f64) -> Self {
+        Self {
+            price: Decimal::from_f64(price).unwrap_or_default().round_dp(8),
+            quantity: Decimal::from_f64(quantity).unwrap_or_default().round_dp(8),
+        }
+    }
+
+    /// Parses a wire-format `[price, quantity]` string pair straight into
+    /// `Decimal`, so a malformed tick surfaces as an `Err` instead of
+    /// silently becoming a phantom zero-quantity level that can wipe a
+    /// price level out of the book.
+    pub fn parse(price: &str, quantity: &str) -> Result<Self, rust_decimal::Error> {
+        Ok(Self {
+            price: Decimal::from_str(price)?,
+            quantity: Decimal::from_str(quantity)?,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,21 +114,21 @@ impl OrderBook {
 
         // Update bids
         for level in &update.bids {
-            let price = OrderedFloat(level.price);
-            if level.quantity == 0.0 {
+            let price = OrderedFloat(level.price.to_f64().unwrap_or(0.0));
+            if level.quantity.is_zero() {
                 self.bids.remove(&price);
             } else {
-                self.bids.insert(price, level.quantity);
+                self.bids.insert(price, level.quantity.to_f64().unwrap_or(0.0));
             }
         }
 
         // Update asks
         for level in &update.asks {
-            let price = OrderedFloat(level.price);
-            if level.quantity == 0.0 {
+            let price = OrderedFloat(level.price.to_f64().unwrap_or(0.0));
+            if level.quantity.is_zero() {
                 self.asks.remove(&price);
             } else {
-                self.asks.insert(price, level.quantity);
+                self.asks.insert(price, level.quantity.to_f64().unwrap_or(0.0));
             }
         }
 
@@ -113,20 +145,14 @@ impl OrderBook {
             .iter()
             .rev()
             .take(depth)
-            .map(|(price, qty)| PriceLevel {
-                price: price.0,
-                quantity: *qty,
-            })
+            .map(|(price, qty)| PriceLevel::new(price.0, *qty))
             .collect();
 
         let asks: Vec<PriceLevel> = self
             .asks
             .iter()
             .take(depth)
-            .map(|(price, qty)| PriceLevel {
-                price: price.0,
-                quantity: *qty,
-            })
+            .map(|(price, qty)| PriceLevel::new(price.0, *qty))
             .collect();
 
         (bids, asks)
@@ -145,6 +171,98 @@ impl OrderBook {
         let best_ask = self.asks.iter().next()?.0.0;
         Some(best_ask - best_bid)
     }
+
+    /// Get spread as basis points of the mid price
+    pub fn spread_bps(&self) -> Option<f64> {
+        let spread = self.spread()?;
+        let mid = self.mid_price()?;
+        if mid == 0.0 {
+            return None;
+        }
+        Some(spread / mid * 10_000.0)
+    }
+
+    /// Best bid price and quantity
+    pub fn best_bid(&self) -> Option<(f64, f64)> {
+        self.bids.iter().next_back().map(|(p, q)| (p.0, *q))
+    }
+
+    /// Best ask price and quantity
+    pub fn best_ask(&self) -> Option<(f64, f64)> {
+        self.asks.iter().next().map(|(p, q)| (p.0, *q))
+    }
+
+    /// Microprice: mid price weighted by the opposite side's size at the
+    /// touch, biasing toward the side with less liquidity (i.e. the side
+    /// more likely to move next).
+    pub fn micro_price(&self) -> Option<f64> {
+        let (best_bid, bid_qty) = self.best_bid()?;
+        let (best_ask, ask_qty) = self.best_ask()?;
+        let total_qty = bid_qty + ask_qty;
+        if total_qty == 0.0 {
+            return None;
+        }
+        Some((best_bid * ask_qty + best_ask * bid_qty) / total_qty)
+    }
+
+    /// Order-book imbalance over the top `depth` levels on each side, in
+    /// `[-1.0, 1.0]`: positive means more bid volume than ask volume.
+    pub fn imbalance(&self, depth: usize) -> Option<f64> {
+        let bid_volume: f64 = self.bids.iter().rev().take(depth).map(|(_, q)| q).sum();
+        let ask_volume: f64 = self.asks.iter().take(depth).map(|(_, q)| q).sum();
+        let total_volume = bid_volume + ask_volume;
+        if total_volume == 0.0 {
+            return None;
+        }
+        Some((bid_volume - ask_volume) / total_volume)
+    }
+
+    /// Walks `side` of the book consuming levels until `quantity` is
+    /// filled, returning the volume-weighted average execution price (0.0
+    /// if nothing could fill) and the quantity that couldn't be filled
+    /// against current liquidity.
+    pub fn vwap_for_quantity(&self, side: Side, quantity: f64) -> (f64, f64) {
+        let levels: Vec<(f64, f64)> = match side {
+            Side::Bid => self.bids.iter().rev().map(|(p, q)| (p.0, *q)).collect(),
+            Side::Ask => self.asks.iter().map(|(p, q)| (p.0, *q)).collect(),
+        };
+
+        let mut remaining = quantity;
+        let mut notional = 0.0;
+        let mut filled = 0.0;
+        for (price, available) in levels {
+            if remaining <= 0.0 {
+                break;
+            }
+            let take = available.min(remaining);
+            notional += take * price;
+            filled += take;
+            remaining -= take;
+        }
+
+        let avg_price = if filled > 0.0 { notional / filled } else { 0.0 };
+        (avg_price, remaining.max(0.0))
+    }
+
+    /// Cumulative volume available on `side` at or better than `price`
+    /// (at-or-above for bids, at-or-below for asks).
+    pub fn depth_at_price(&self, side: Side, price: f64) -> f64 {
+        match side {
+            Side::Bid => self
+                .bids
+                .iter()
+                .rev()
+                .take_while(|(p, _)| p.0 >= price)
+                .map(|(_, q)| q)
+                .sum(),
+            Side::Ask => self
+                .asks
+                .iter()
+                .take_while(|(p, _)| p.0 <= price)
+                .map(|(_, q)| q)
+                .sum(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -156,18 +274,90 @@ pub struct DepthUpdate {
     pub asks: Vec<PriceLevel>,
 }
 
+/// A single executed trade, carrying full exchange precision. Consumers
+/// that need `f64` (candle math, the backtest engine, the shm codec)
+/// convert via `to_f64()` at their own boundary rather than this type
+/// fabricating a lossy value up front.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Trade {
     pub symbol: String,
     pub trade_id: u64,
-    pub price: f64,
-    pub quantity: f64,
+    pub price: Decimal,
+    pub quantity: Decimal,
     pub timestamp: i64,
     pub is_buyer_maker: bool,
 }
 
+impl Trade {
+    /// Convenience constructor for call sites that already have `f64`
+    /// values (tests, synthetic trades). Real exchange ticks should be
+    /// parsed directly via `Decimal::from_str`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        symbol: impl Into<String>,
+        trade_id: u64,
+        price: f64,
+        quantity: f64,
+        timestamp: i64,
+        is_buyer_maker: bool,
+    ) -> Self {
+        Self {
+            symbol: symbol.into(),
+            trade_id,
+            price: Decimal::from_f64(price).unwrap_or_default().round_dp(8),
+            quantity: Decimal::from_f64(quantity).unwrap_or_default().round_dp(8),
+            timestamp,
+            is_buyer_maker,
+        }
+    }
+
+    /// Parses a wire-format trade's `price`/`quantity` strings straight into
+    /// `Decimal`, propagating a parse failure instead of fabricating `0.0`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn parse(
+        symbol: impl Into<String>,
+        trade_id: u64,
+        price: &str,
+        quantity: &str,
+        timestamp: i64,
+        is_buyer_maker: bool,
+    ) -> Result<Self, rust_decimal::Error> {
+        Ok(Self {
+            symbol: symbol.into(),
+            trade_id,
+            price: Decimal::from_str(price)?,
+            quantity: Decimal::from_str(quantity)?,
+            timestamp,
+            is_buyer_maker,
+        })
+    }
+}
+
+/// Per-symbol resync state for `OrderBookManager::update`. A symbol starts
+/// (and normally stays) `Synced`; a detected sequence gap moves it to
+/// `Syncing`, where diffs pile up until `apply_snapshot` finds one that
+/// bridges a fresh REST snapshot and replays it back to `Synced`.
+#[derive(Debug, Clone)]
+enum SyncState {
+    Synced,
+    Syncing { buffered: Vec<DepthUpdate> },
+}
+
+/// Result of feeding a live diff into `OrderBookManager::update`.
+#[derive(Debug)]
+pub enum UpdateOutcome {
+    /// Applied cleanly; carries the refreshed book.
+    Applied(OrderBook),
+    /// A gap was detected for this symbol; it is now `Syncing` and the
+    /// caller should fetch a fresh REST snapshot and call `apply_snapshot`.
+    ResyncRequired,
+    /// The symbol is already `Syncing`; the diff was buffered for replay.
+    Buffered,
+}
+
 pub struct OrderBookManager {
     books: RwLock<AHashMap<String, OrderBook>>,
+    sync_state: RwLock<AHashMap<String, SyncState>>,
     depth: usize,
 }
 
@@ -175,6 +365,7 @@ impl OrderBookManager {
     pub fn new(depth: usize) -> Self {
         Self {
             books: RwLock::new(AHashMap::new()),
+            sync_state: RwLock::new(AHashMap::new()),
             depth,
         }
     }
@@ -193,14 +384,109 @@ impl OrderBookManager {
             .clone()
     }
 
-    pub fn update(&self, symbol: &str, update: DepthUpdate) -> Result<OrderBook, String> {
+    /// Self-healing delta applier: a sequence gap no longer returns an
+    /// unrecoverable `Err`. Instead the symbol flips to `Syncing`, the diff
+    /// that revealed the gap is kept for replay, and the caller is signalled
+    /// via `UpdateOutcome::ResyncRequired` to go fetch a fresh snapshot.
+    pub fn update(&self, symbol: &str, update: DepthUpdate) -> UpdateOutcome {
+        {
+            let mut sync_state = self.sync_state.write().unwrap();
+            if let Some(SyncState::Syncing { buffered }) = sync_state.get_mut(symbol) {
+                buffered.push(update);
+                return UpdateOutcome::Buffered;
+            }
+        }
+
         let mut books = self.books.write().unwrap();
         let book = books
             .entry(symbol.to_string())
             .or_insert_with(|| OrderBook::new(symbol.to_string()));
 
-        book.apply_update(&update)?;
-        Ok(book.clone())
+        match book.apply_update(&update) {
+            Ok(()) => UpdateOutcome::Applied(book.clone()),
+            Err(_) => {
+                drop(books);
+                self.sync_state.write().unwrap().insert(
+                    symbol.to_string(),
+                    SyncState::Syncing { buffered: vec![update] },
+                );
+                UpdateOutcome::ResyncRequired
+            }
+        }
+    }
+
+    /// Mark `symbol` as needing a fresh snapshot, discarding anything it had
+    /// previously buffered. Used to kick off the very first sync, rather
+    /// than waiting for a live diff to surface a gap.
+    pub fn mark_syncing(&self, symbol: &str) {
+        self.sync_state
+            .write()
+            .unwrap()
+            .insert(symbol.to_string(), SyncState::Syncing { buffered: Vec::new() });
+    }
+
+    pub fn is_syncing(&self, symbol: &str) -> bool {
+        matches!(
+            self.sync_state.read().unwrap().get(symbol),
+            Some(SyncState::Syncing { .. })
+        )
+    }
+
+    /// Resolves a REST `snapshot` (bids/asks plus `last_update_id`) against
+    /// whatever diffs were buffered for `symbol`, following Binance's
+    /// documented depth-cache procedure: discard buffered diffs that predate
+    /// the snapshot, find the first one that bridges it
+    /// (`first_update_id <= snapshot_id + 1 <= last_update_id`), then apply
+    /// it and every later diff in order while enforcing contiguity. Returns
+    /// `Err` (leaving the symbol `Syncing`, buffering further diffs) if no
+    /// buffered diff bridges the snapshot yet or contiguity breaks mid-replay.
+    pub fn apply_snapshot(&self, symbol: &str, snapshot: OrderBook) -> Result<OrderBook, String> {
+        let mut sync_state = self.sync_state.write().unwrap();
+        let buffered = match sync_state.get_mut(symbol) {
+            Some(SyncState::Syncing { buffered }) => std::mem::take(buffered),
+            _ => Vec::new(),
+        };
+
+        let snapshot_id = snapshot.last_update_id;
+        let mut buffered: Vec<DepthUpdate> = buffered
+            .into_iter()
+            .filter(|u| u.last_update_id >= snapshot_id)
+            .collect();
+        buffered.sort_by_key(|u| u.first_update_id);
+
+        let bridge_idx = buffered
+            .iter()
+            .position(|u| u.first_update_id <= snapshot_id + 1 && u.last_update_id >= snapshot_id + 1);
+
+        let Some(bridge_idx) = bridge_idx else {
+            sync_state.insert(symbol.to_string(), SyncState::Syncing { buffered });
+            return Err(format!(
+                "no buffered diff bridges snapshot {} for {}, still syncing",
+                snapshot_id, symbol
+            ));
+        };
+
+        let mut book = snapshot;
+        for (i, update) in buffered.split_off(bridge_idx).into_iter().enumerate() {
+            // The bridging diff (i == 0) only has to satisfy
+            // `first_update_id <= snapshot_id + 1`, not strict contiguity
+            // with the snapshot's `last_update_id` -- that's the whole point
+            // of "bridging". Strict contiguity only applies to diffs after it.
+            if i > 0 && update.first_update_id != book.last_update_id + 1 {
+                sync_state.insert(symbol.to_string(), SyncState::Syncing { buffered: Vec::new() });
+                return Err(format!(
+                    "contiguity gap replaying buffered diffs for {}, resyncing",
+                    symbol
+                ));
+            }
+            book.apply_update(&update)?;
+        }
+
+        sync_state.insert(symbol.to_string(), SyncState::Synced);
+        drop(sync_state);
+
+        self.books.write().unwrap().insert(symbol.to_string(), book.clone());
+        Ok(book)
     }
 
     pub fn get(&self, symbol: &str) -> Option<OrderBook> {
@@ -208,6 +494,14 @@ impl OrderBookManager {
         books.get(symbol).cloned()
     }
 
+    /// Overwrite the book for `symbol` with one already synchronized
+    /// elsewhere (e.g. by a `DepthSynchronizer`), rather than applying a
+    /// delta on top of whatever this manager currently holds.
+    pub fn set(&self, symbol: &str, book: OrderBook) {
+        let mut books = self.books.write().unwrap();
+        books.insert(symbol.to_string(), book);
+    }
+
     pub fn snapshot(&self, symbol: &str, depth: usize) -> Option<(Vec<PriceLevel>, Vec<PriceLevel>)> {
         let books = self.books.read().unwrap();
         books.get(symbol).map(|book| book.get_top_levels(depth))
@@ -227,12 +521,12 @@ mod tests {
             first_update_id: 1,
             last_update_id: 1,
             bids: vec![
-                PriceLevel { price: 50000.0, quantity: 1.5 },
-                PriceLevel { price: 49999.0, quantity: 2.0 },
+                PriceLevel::new(50000.0, 1.5),
+                PriceLevel::new(49999.0, 2.0),
             ],
             asks: vec![
-                PriceLevel { price: 50001.0, quantity: 1.0 },
-                PriceLevel { price: 50002.0, quantity: 3.0 },
+                PriceLevel::new(50001.0, 1.0),
+                PriceLevel::new(50002.0, 3.0),
             ],
         };
 
@@ -244,6 +538,46 @@ mod tests {
         assert!((mid - 50000.5).abs() < 0.01);
     }
 
+    #[test]
+    fn test_vwap_for_quantity_walks_levels_and_reports_unfilled() {
+        let mut book = OrderBook::new("BTCUSDT".to_string());
+        book.apply_update(&DepthUpdate {
+            symbol: "BTCUSDT".to_string(),
+            first_update_id: 1,
+            last_update_id: 1,
+            bids: vec![],
+            asks: vec![
+                PriceLevel::new(100.0, 1.0),
+                PriceLevel::new(101.0, 1.0),
+            ],
+        }).unwrap();
+
+        let (avg_price, unfilled) = book.vwap_for_quantity(Side::Ask, 1.5);
+        assert!((avg_price - (100.0 * 1.0 + 101.0 * 0.5) / 1.5).abs() < 1e-9);
+        assert_eq!(unfilled, 0.0);
+
+        let (_, unfilled) = book.vwap_for_quantity(Side::Ask, 5.0);
+        assert_eq!(unfilled, 3.0);
+    }
+
+    #[test]
+    fn test_depth_at_price_sums_cumulative_volume() {
+        let mut book = OrderBook::new("BTCUSDT".to_string());
+        book.apply_update(&DepthUpdate {
+            symbol: "BTCUSDT".to_string(),
+            first_update_id: 1,
+            last_update_id: 1,
+            bids: vec![
+                PriceLevel::new(100.0, 1.0),
+                PriceLevel::new(99.0, 2.0),
+            ],
+            asks: vec![],
+        }).unwrap();
+
+        assert_eq!(book.depth_at_price(Side::Bid, 100.0), 1.0);
+        assert_eq!(book.depth_at_price(Side::Bid, 99.0), 3.0);
+    }
+
     #[test]
     fn test_sequence_validation() {
         let mut book = OrderBook::new("BTCUSDT".to_string());
@@ -259,4 +593,81 @@ mod tests {
 
         assert!(book.apply_update(&update).is_err());
     }
+
+    #[test]
+    fn test_manager_signals_resync_on_gap_instead_of_erroring() {
+        let manager = OrderBookManager::new(10);
+
+        let first = DepthUpdate {
+            symbol: "BTCUSDT".to_string(),
+            first_update_id: 1,
+            last_update_id: 5,
+            bids: vec![PriceLevel::new(100.0, 1.0)],
+            asks: vec![],
+        };
+        assert!(matches!(manager.update("BTCUSDT", first), UpdateOutcome::Applied(_)));
+
+        // A gapped diff should not be an unrecoverable Err: the symbol flips
+        // to syncing and subsequent diffs buffer instead of applying.
+        let gapped = DepthUpdate {
+            symbol: "BTCUSDT".to_string(),
+            first_update_id: 10,
+            last_update_id: 12,
+            bids: vec![],
+            asks: vec![],
+        };
+        assert!(matches!(manager.update("BTCUSDT", gapped), UpdateOutcome::ResyncRequired));
+        assert!(manager.is_syncing("BTCUSDT"));
+
+        let buffered = DepthUpdate {
+            symbol: "BTCUSDT".to_string(),
+            first_update_id: 13,
+            last_update_id: 15,
+            bids: vec![],
+            asks: vec![],
+        };
+        assert!(matches!(manager.update("BTCUSDT", buffered), UpdateOutcome::Buffered));
+    }
+
+    #[test]
+    fn test_apply_snapshot_bridges_buffered_diffs() {
+        let manager = OrderBookManager::new(10);
+        manager.mark_syncing("BTCUSDT");
+
+        // Buffered while syncing: one stale diff (predates the snapshot),
+        // one that bridges it, and one contiguous diff after the bridge.
+        for update in [
+            DepthUpdate {
+                symbol: "BTCUSDT".to_string(),
+                first_update_id: 90,
+                last_update_id: 98,
+                bids: vec![],
+                asks: vec![],
+            },
+            DepthUpdate {
+                symbol: "BTCUSDT".to_string(),
+                first_update_id: 99,
+                last_update_id: 101,
+                bids: vec![PriceLevel::new(100.0, 2.0)],
+                asks: vec![],
+            },
+            DepthUpdate {
+                symbol: "BTCUSDT".to_string(),
+                first_update_id: 102,
+                last_update_id: 103,
+                bids: vec![PriceLevel::new(100.0, 3.0)],
+                asks: vec![],
+            },
+        ] {
+            assert!(matches!(manager.update("BTCUSDT", update), UpdateOutcome::Buffered));
+        }
+
+        let mut snapshot = OrderBook::new("BTCUSDT".to_string());
+        snapshot.last_update_id = 100;
+
+        let book = manager.apply_snapshot("BTCUSDT", snapshot).unwrap();
+        assert_eq!(book.last_update_id, 103);
+        assert_eq!(book.bids.get(&OrderedFloat(100.0)), Some(&3.0));
+        assert!(!manager.is_syncing("BTCUSDT"));
+    }
 }
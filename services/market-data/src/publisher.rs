@@ -2,18 +2,97 @@ use anyhow::{Result, Context};
 use redis::aio::ConnectionManager;
 use redis::{AsyncCommands, Client};
 use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::{debug, error};
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::sleep;
+use tracing::{debug, error, warn};
 
+use crate::candles::{Candle, CandleAggregator, Resolution};
+use crate::exchange::{BookTicker, Kline};
 use crate::orderbook::{OrderBook, Trade};
 use crate::shm::SharedMemoryRing;
+use crate::sinks::MarketDataSink;
+use crate::storage::StorageWriter;
+use crate::ticker::RollingWindow;
+use crate::user_stream::{Alert, Fill, Position};
+use crate::wire::{self, SymbolRegistry};
 use crate::metrics;
 
+/// Errors from a single publish/store attempt against Redis or shared
+/// memory. Kept distinct from the `anyhow::Error` the public `publish_*`
+/// methods return so `publish_redis`/`set_redis` can classify connection-level
+/// failures (and trigger a reconnect) without string-matching an opaque error.
+#[derive(Debug, thiserror::Error)]
+pub enum PublishError {
+    #[error("failed to serialize payload: {0}")]
+    Serialize(#[from] serde_json::Error),
+
+    #[error("Redis PUBLISH failed: {0}")]
+    RedisPublish(#[source] redis::RedisError),
+
+    #[error("Redis SET failed: {0}")]
+    RedisSet(#[source] redis::RedisError),
+
+    #[error("Redis XADD failed: {0}")]
+    RedisStream(#[source] redis::RedisError),
+
+    #[error("shared-memory write failed: {0}")]
+    ShmWrite(String),
+
+    #[error("Redis connection is down, message queued for replay")]
+    Disconnected,
+}
+
+/// Connection health as seen by callers of `Publisher::health_check`: either
+/// the live `ConnectionManager` answered a `PING`, or the background
+/// reconnect loop is currently rebuilding it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connected,
+    Reconnecting,
+}
+
+/// A publish/store call that failed because Redis was unreachable, held so
+/// the reconnect loop can replay it once a new `ConnectionManager` is up.
+#[derive(Debug, Clone)]
+enum OutboundMessage {
+    Publish { channel: String, payload: String },
+    Set { key: String, payload: String, ttl_seconds: u64 },
+    Stream { key: String, payload: String, maxlen: usize },
+}
+
+/// Outbox messages queued while Redis is unreachable; oldest is dropped on
+/// overflow rather than blocking the publish path or growing unbounded.
+const OUTBOX_CAPACITY: usize = 1000;
+
+/// Approximate cap (`MAXLEN ~`) on each `fills:{symbol}` Redis stream, so a
+/// late-joining consumer (e.g. the TUI reconnecting) can replay recent fills
+/// without the stream growing unbounded.
+const FILLS_STREAM_MAXLEN: usize = 1000;
+
+const RECONNECT_BASE_DELAY: Duration = Duration::from_millis(100);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
 pub struct Publisher {
     redis_client: Client,
     redis_conn: Arc<RwLock<ConnectionManager>>,
+    /// Set while the background reconnect loop is rebuilding `redis_conn`.
+    /// `compare_exchange`'d so only one reconnect loop is ever in flight.
+    reconnecting: Arc<AtomicBool>,
+    outbox: Arc<Mutex<VecDeque<OutboundMessage>>>,
     shm_ring: Arc<SharedMemoryRing>,
+    symbol_registry: SymbolRegistry,
+    candle_aggregator: Arc<CandleAggregator>,
+    ticker_window: RollingWindow,
+    /// Additional backends mirroring order books and trades alongside the
+    /// Redis/shared-memory path above (see `sinks` module docs).
+    sinks: Vec<Arc<dyn MarketDataSink>>,
+    /// Batched persistence of trades/finalized candles into Postgres/
+    /// TimescaleDB for historical queries, if configured (see `storage`).
+    storage: Option<StorageWriter>,
 }
 
 #[derive(Serialize)]
@@ -29,7 +108,14 @@ struct MarketData {
 }
 
 impl Publisher {
-    pub async fn new(redis_url: &str, shm_name: &str) -> Result<Self> {
+    pub async fn new(
+        redis_url: &str,
+        shm_name: &str,
+        candle_aggregator: Arc<CandleAggregator>,
+        ticker_window_secs: u64,
+        sinks: Vec<Arc<dyn MarketDataSink>>,
+        storage: Option<StorageWriter>,
+    ) -> Result<Self> {
         let redis_client = Client::open(redis_url)
             .context("Failed to create Redis client")?;
 
@@ -43,10 +129,46 @@ impl Publisher {
         Ok(Self {
             redis_client,
             redis_conn: Arc::new(RwLock::new(conn_manager)),
+            reconnecting: Arc::new(AtomicBool::new(false)),
+            outbox: Arc::new(Mutex::new(VecDeque::new())),
             shm_ring: Arc::new(shm_ring),
+            symbol_registry: SymbolRegistry::new(),
+            candle_aggregator,
+            ticker_window: RollingWindow::new(ticker_window_secs),
+            sinks,
+            storage,
         })
     }
 
+    /// Fans `book`/`trade` (exactly one of which is `Some`) out to every
+    /// configured sink concurrently, so a slow or down backend doesn't delay
+    /// the others. Sink failures are counted and logged, never propagated --
+    /// a mirrored backend being unavailable must not affect the primary
+    /// Redis/shm path above.
+    async fn publish_to_sinks(&self, book: Option<&OrderBook>, trade: Option<&Trade>) {
+        let kind = if book.is_some() { "orderbook" } else { "trade" };
+
+        let publishes = self.sinks.iter().map(|sink| async move {
+            let result = match (book, trade) {
+                (Some(book), _) => sink.publish_orderbook(book).await,
+                (_, Some(trade)) => sink.publish_trade(trade).await,
+                (None, None) => return,
+            };
+
+            match result {
+                Ok(()) => {
+                    metrics::SINK_PUBLISHES.with_label_values(&[sink.name(), kind]).inc();
+                }
+                Err(e) => {
+                    error!("Sink {} failed to publish {}: {}", sink.name(), kind, e);
+                    metrics::SINK_ERRORS.with_label_values(&[sink.name(), kind]).inc();
+                }
+            }
+        });
+
+        futures_util::future::join_all(publishes).await;
+    }
+
     pub async fn publish_orderbook(&self, book: &OrderBook) -> Result<()> {
         // 1. Publish full orderbook to orderbook:SYMBOL channel
         let orderbook_channel = format!("orderbook:{}", book.symbol);
@@ -76,14 +198,16 @@ impl Publisher {
             0.0
         };
 
+        let ticker_stats = self.ticker_window.current(&book.symbol);
+
         let market_data = MarketData {
             symbol: book.symbol.clone(),
             last_price,
             bid_price: best_bid,
             ask_price: best_ask,
-            volume_24h: 0.0, // TODO: Track from trades
-            high_24h: 0.0,   // TODO: Track from trades
-            low_24h: 0.0,    // TODO: Track from trades
+            volume_24h: ticker_stats.volume,
+            high_24h: ticker_stats.high,
+            low_24h: ticker_stats.low,
             updated_at: chrono::Utc::now().to_rfc3339(),
         };
 
@@ -119,9 +243,12 @@ impl Publisher {
 
         // 4. Write full orderbook to shared memory for ultra-low latency local consumers
         if let Err(e) = self.shm_ring.write(orderbook_payload.as_bytes()) {
-            error!("Failed to write to shared memory: {}", e);
+            error!("{}", PublishError::ShmWrite(e.to_string()));
         }
 
+        // 5. Mirror to any configured pluggable sinks (e.g. NATS JetStream)
+        self.publish_to_sinks(Some(book), None).await;
+
         debug!("Published order book and market data for {}", book.symbol);
         Ok(())
     }
@@ -146,31 +273,459 @@ impl Publisher {
             }
         }
 
+        // Also write a compact fixed-width record to shared memory: at
+        // trade frequency, JSON-in-a-ring is both slower to parse and more
+        // likely to blow the slot size than a constant 32-byte record.
+        let symbol_code = self.symbol_registry.register(&trade.symbol);
+        let server_time_ms = chrono::Utc::now().timestamp_millis() as u32;
+        let record = wire::TradeRecord::from_trade(trade, symbol_code, server_time_ms);
+        let mut buf = [0u8; wire::SERIALIZED_SIZE];
+        if let Err(e) = wire::encode_into(&mut buf, &record) {
+            error!("Failed to encode trade for shared memory: {}", e);
+        } else if let Err(e) = self.shm_ring.write(&buf) {
+            error!("{}", PublishError::ShmWrite(e.to_string()));
+        }
+
         debug!("Published trade for {}", trade.symbol);
+
+        // Mirror to any configured pluggable sinks (e.g. NATS JetStream)
+        self.publish_to_sinks(None, Some(trade)).await;
+
+        // Persist for historical range queries, if a storage backend is configured.
+        if let Some(storage) = &self.storage {
+            storage.record_trade(trade.clone());
+        }
+
+        // Roll the trade into every tracked candle resolution: publish any
+        // candle this trade's bucket crossing just closed, then snapshot the
+        // now-current candle for every resolution so a dashboard polling
+        // `candle:{interval}:{symbol}` always sees the latest in-progress bar.
+        for candle in self.candle_aggregator.record_trade(trade) {
+            if let Err(e) = self.publish_candle(&candle).await {
+                error!("Failed to publish candle: {}", e);
+                metrics::REDIS_ERRORS.with_label_values(&[&candle.symbol]).inc();
+            }
+        }
+        for resolution in Resolution::all() {
+            if let Some(active) = self.candle_aggregator.active_candle(&trade.symbol, resolution) {
+                if let Err(e) = self.store_active_candle(&active).await {
+                    error!("Failed to store active candle: {}", e);
+                    metrics::REDIS_ERRORS.with_label_values(&[&active.symbol]).inc();
+                }
+            }
+        }
+
+        // Fold the trade into the trailing ticker window so the next
+        // `publish_orderbook` call reports a live volume/high/low instead
+        // of the zeros it started with.
+        self.ticker_window.record_trade(trade);
+
         Ok(())
     }
 
-    async fn publish_redis(&self, channel: &str, payload: &str) -> Result<()> {
-        let mut conn = self.redis_conn.write().await;
-        conn.publish::<_, _, ()>(channel, payload)
-            .await
-            .context("Redis publish failed")?;
+    /// Publishes a just-closed candle to `candles:{interval}:{symbol}` and
+    /// writes it to shared memory, so a local consumer can pick up a closed
+    /// bar without round-tripping through Redis.
+    async fn publish_candle(&self, candle: &Candle) -> Result<(), PublishError> {
+        let channel = format!("candles:{}:{}", candle.resolution, candle.symbol);
+        let payload = serde_json::to_string(candle)?;
+
+        self.publish_redis(&channel, &payload).await?;
+        metrics::REDIS_PUBLISHES
+            .with_label_values(&[&candle.symbol, "candle"])
+            .inc();
+
+        if let Err(e) = self.shm_ring.write(payload.as_bytes()) {
+            error!("{}", PublishError::ShmWrite(e.to_string()));
+        }
+
+        // Persist the finalized candle for historical queries and
+        // backfill-on-restart, if a storage backend is configured.
+        if let Some(storage) = &self.storage {
+            storage.record_candle(candle.clone());
+        }
+
         Ok(())
     }
 
-    async fn set_redis(&self, key: &str, value: &str, ttl_seconds: u64) -> Result<()> {
-        let mut conn = self.redis_conn.write().await;
-        conn.set_ex::<_, _, ()>(key, value, ttl_seconds)
-            .await
-            .context("Redis SET failed")?;
+    /// Stores the current in-progress candle under `candle:{interval}:{symbol}`,
+    /// overwriting it on every trade so readers always see the live bar.
+    async fn store_active_candle(&self, candle: &Candle) -> Result<(), PublishError> {
+        let key = format!("candle:{}:{}", candle.resolution, candle.symbol);
+        let payload = serde_json::to_string(candle)?;
+
+        self.set_redis(&key, &payload, 300).await
+    }
+
+    /// Publishes an account position update from the user-data stream, so
+    /// downstream account monitors (e.g. the TUI's positions panel) see
+    /// live state rather than externally-injected data.
+    pub async fn publish_position(&self, position: &Position) -> Result<()> {
+        let channel = format!("positions:{}", position.symbol);
+        let payload = serde_json::to_string(position)
+            .context("Failed to serialize position")?;
+
+        match self.publish_redis(&channel, &payload).await {
+            Ok(_) => {
+                metrics::REDIS_PUBLISHES
+                    .with_label_values(&[&position.symbol, "position"])
+                    .inc();
+            }
+            Err(e) => {
+                error!("Failed to publish position to Redis: {}", e);
+                metrics::REDIS_ERRORS
+                    .with_label_values(&[&position.symbol])
+                    .inc();
+            }
+        }
+
         Ok(())
     }
 
-    pub async fn health_check(&self) -> bool {
-        let mut conn = self.redis_conn.write().await;
-        redis::cmd("PING")
-            .query_async::<_, String>(&mut *conn)
+    /// Publishes a kline/candlestick update from the exchange's native
+    /// kline stream (distinct from `CandleAggregator`, which derives candles
+    /// locally from the trade stream).
+    pub async fn publish_kline(&self, kline: &Kline) -> Result<()> {
+        let channel = format!("klines:{}:{}", kline.symbol, kline.interval);
+        let payload = serde_json::to_string(kline)
+            .context("Failed to serialize kline")?;
+
+        match self.publish_redis(&channel, &payload).await {
+            Ok(_) => {
+                metrics::REDIS_PUBLISHES
+                    .with_label_values(&[&kline.symbol, "kline"])
+                    .inc();
+            }
+            Err(e) => {
+                error!("Failed to publish kline to Redis: {}", e);
+                metrics::REDIS_ERRORS
+                    .with_label_values(&[&kline.symbol])
+                    .inc();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Publishes a best-bid/ask update from the exchange's `bookTicker`
+    /// stream: a lower-latency top-of-book than waiting on a full depth diff.
+    pub async fn publish_book_ticker(&self, ticker: &BookTicker) -> Result<()> {
+        let channel = format!("book_ticker:{}", ticker.symbol);
+        let payload = serde_json::to_string(ticker)
+            .context("Failed to serialize book ticker")?;
+
+        match self.publish_redis(&channel, &payload).await {
+            Ok(_) => {
+                metrics::REDIS_PUBLISHES
+                    .with_label_values(&[&ticker.symbol, "book_ticker"])
+                    .inc();
+            }
+            Err(e) => {
+                error!("Failed to publish book ticker to Redis: {}", e);
+                metrics::REDIS_ERRORS
+                    .with_label_values(&[&ticker.symbol])
+                    .inc();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Publishes an account alert (rejected or expired orders, and new orders
+    /// carrying a reject reason) from the user-data stream to a
+    /// symbol-agnostic channel. Liquidation orders are not flagged.
+    pub async fn publish_alert(&self, alert: &Alert) -> Result<()> {
+        let payload = serde_json::to_string(alert)
+            .context("Failed to serialize alert")?;
+        let label = alert.symbol.as_deref().unwrap_or("*");
+
+        match self.publish_redis("alerts", &payload).await {
+            Ok(_) => {
+                metrics::REDIS_PUBLISHES
+                    .with_label_values(&[label, "alert"])
+                    .inc();
+            }
+            Err(e) => {
+                error!("Failed to publish alert to Redis: {}", e);
+                metrics::REDIS_ERRORS.with_label_values(&[label]).inc();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Publishes a fill from the user-data stream to `fills:{symbol}` and
+    /// appends it to a capped Redis stream of the same name, so the TUI's
+    /// fills panel can both subscribe live and replay recent history on
+    /// reconnect instead of relying on a separate ad-hoc feed.
+    pub async fn publish_fill(&self, fill: &Fill) -> Result<()> {
+        let channel = format!("fills:{}", fill.symbol);
+        let payload = serde_json::to_string(fill)
+            .context("Failed to serialize fill")?;
+
+        match self.publish_redis(&channel, &payload).await {
+            Ok(_) => {
+                metrics::REDIS_PUBLISHES
+                    .with_label_values(&[&fill.symbol, "fill"])
+                    .inc();
+            }
+            Err(e) => {
+                error!("Failed to publish fill to Redis: {}", e);
+                metrics::REDIS_ERRORS
+                    .with_label_values(&[&fill.symbol])
+                    .inc();
+            }
+        }
+
+        if let Err(e) = self.stream_redis(&channel, &payload, FILLS_STREAM_MAXLEN).await {
+            error!("Failed to append fill to Redis stream: {}", e);
+            metrics::REDIS_ERRORS
+                .with_label_values(&[&fill.symbol])
+                .inc();
+        }
+
+        Ok(())
+    }
+
+    async fn publish_redis(&self, channel: &str, payload: &str) -> Result<(), PublishError> {
+        if self.is_reconnecting() {
+            self.enqueue(OutboundMessage::Publish {
+                channel: channel.to_string(),
+                payload: payload.to_string(),
+            })
+            .await;
+            return Err(PublishError::Disconnected);
+        }
+
+        let result = {
+            let mut conn = self.redis_conn.write().await;
+            conn.publish::<_, _, ()>(channel, payload).await
+        };
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                if Self::is_connection_error(&e) {
+                    self.enqueue(OutboundMessage::Publish {
+                        channel: channel.to_string(),
+                        payload: payload.to_string(),
+                    })
+                    .await;
+                    self.on_connection_error(&e);
+                }
+                Err(PublishError::RedisPublish(e))
+            }
+        }
+    }
+
+    async fn set_redis(&self, key: &str, value: &str, ttl_seconds: u64) -> Result<(), PublishError> {
+        if self.is_reconnecting() {
+            self.enqueue(OutboundMessage::Set {
+                key: key.to_string(),
+                payload: value.to_string(),
+                ttl_seconds,
+            })
+            .await;
+            return Err(PublishError::Disconnected);
+        }
+
+        let result = {
+            let mut conn = self.redis_conn.write().await;
+            conn.set_ex::<_, _, ()>(key, value, ttl_seconds).await
+        };
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                if Self::is_connection_error(&e) {
+                    self.enqueue(OutboundMessage::Set {
+                        key: key.to_string(),
+                        payload: value.to_string(),
+                        ttl_seconds,
+                    })
+                    .await;
+                    self.on_connection_error(&e);
+                }
+                Err(PublishError::RedisSet(e))
+            }
+        }
+    }
+
+    /// Appends `payload` to the Redis stream `key`, trimmed to approximately
+    /// `maxlen` entries (`MAXLEN ~`, so trimming stays cheap and doesn't have
+    /// to walk the whole stream on every call).
+    async fn stream_redis(&self, key: &str, payload: &str, maxlen: usize) -> Result<(), PublishError> {
+        if self.is_reconnecting() {
+            self.enqueue(OutboundMessage::Stream {
+                key: key.to_string(),
+                payload: payload.to_string(),
+                maxlen,
+            })
+            .await;
+            return Err(PublishError::Disconnected);
+        }
+
+        let result = {
+            let mut conn = self.redis_conn.write().await;
+            conn.xadd_maxlen::<_, _, _, _, ()>(
+                key,
+                redis::streams::StreamMaxlen::Approx(maxlen),
+                "*",
+                &[("payload", payload)],
+            )
             .await
-            .is_ok()
+        };
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                if Self::is_connection_error(&e) {
+                    self.enqueue(OutboundMessage::Stream {
+                        key: key.to_string(),
+                        payload: payload.to_string(),
+                        maxlen,
+                    })
+                    .await;
+                    self.on_connection_error(&e);
+                }
+                Err(PublishError::RedisStream(e))
+            }
+        }
+    }
+
+    fn is_reconnecting(&self) -> bool {
+        self.reconnecting.load(Ordering::SeqCst)
+    }
+
+    /// Whether `err` reflects a dead connection (as opposed to e.g. a bad
+    /// command) -- the only case worth tearing down and rebuilding the
+    /// `ConnectionManager` over.
+    fn is_connection_error(err: &redis::RedisError) -> bool {
+        err.is_connection_dropped() || err.is_io_error() || err.is_unrecoverable_error()
+    }
+
+    async fn enqueue(&self, message: OutboundMessage) {
+        let mut outbox = self.outbox.lock().await;
+        if outbox.len() >= OUTBOX_CAPACITY {
+            outbox.pop_front();
+            let kind = match message {
+                OutboundMessage::Publish { .. } => "publish",
+                OutboundMessage::Set { .. } => "set",
+                OutboundMessage::Stream { .. } => "stream",
+            };
+            metrics::REDIS_DROPPED.with_label_values(&[kind]).inc();
+        }
+        outbox.push_back(message);
+    }
+
+    /// Marks the connection degraded and, if no reconnect loop is already
+    /// running, spawns one. `compare_exchange` ensures concurrent publishers
+    /// hitting the same dead connection only start a single loop.
+    fn on_connection_error(&self, err: &redis::RedisError) {
+        if self
+            .reconnecting
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return;
+        }
+
+        error!("Redis connection lost, starting reconnect loop: {}", err);
+        tokio::spawn(Self::reconnect_loop(
+            self.redis_client.clone(),
+            Arc::clone(&self.redis_conn),
+            Arc::clone(&self.reconnecting),
+            Arc::clone(&self.outbox),
+        ));
+    }
+
+    /// Rebuilds `redis_conn` with exponential backoff (100ms doubling to a
+    /// 30s cap, with jitter to avoid every publisher racing the same retry
+    /// tick), then replays whatever accumulated in `outbox` while it was down.
+    async fn reconnect_loop(
+        client: Client,
+        redis_conn: Arc<RwLock<ConnectionManager>>,
+        reconnecting: Arc<AtomicBool>,
+        outbox: Arc<Mutex<VecDeque<OutboundMessage>>>,
+    ) {
+        let mut delay = RECONNECT_BASE_DELAY;
+        let mut attempt: u32 = 0;
+
+        let new_conn = loop {
+            match client.get_tokio_connection_manager().await {
+                Ok(conn) => break conn,
+                Err(e) => {
+                    attempt += 1;
+                    warn!("Redis reconnect attempt failed, retrying in {:?}: {}", delay, e);
+                    sleep(delay + Duration::from_millis(Self::jitter_ms(attempt))).await;
+                    delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+                }
+            }
+        };
+
+        *redis_conn.write().await = new_conn;
+
+        let pending: Vec<OutboundMessage> = {
+            let mut queue = outbox.lock().await;
+            queue.drain(..).collect()
+        };
+
+        {
+            let mut conn = redis_conn.write().await;
+            for message in pending {
+                let result = match &message {
+                    OutboundMessage::Publish { channel, payload } => {
+                        conn.publish::<_, _, ()>(channel, payload).await
+                    }
+                    OutboundMessage::Set { key, payload, ttl_seconds } => {
+                        conn.set_ex::<_, _, ()>(key, payload, *ttl_seconds).await
+                    }
+                    OutboundMessage::Stream { key, payload, maxlen } => {
+                        conn.xadd_maxlen::<_, _, _, _, ()>(
+                            key,
+                            redis::streams::StreamMaxlen::Approx(*maxlen),
+                            "*",
+                            &[("payload", payload)],
+                        )
+                        .await
+                    }
+                };
+                if let Err(e) = result {
+                    error!("Failed to replay queued message after reconnect: {}", e);
+                }
+            }
+        }
+
+        reconnecting.store(false, Ordering::SeqCst);
+        debug!("Redis connection restored");
+    }
+
+    /// Small bounded jitter (0-49ms) added to each reconnect backoff so
+    /// multiple publishers that lost the connection at the same instant
+    /// don't all retry `get_tokio_connection_manager` on the same tick.
+    fn jitter_ms(attempt: u32) -> u64 {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+
+        let mut hasher = DefaultHasher::new();
+        (attempt, nanos).hash(&mut hasher);
+        hasher.finish() % 50
+    }
+
+    pub async fn health_check(&self) -> ConnectionState {
+        if self.is_reconnecting() {
+            return ConnectionState::Reconnecting;
+        }
+
+        let mut conn = self.redis_conn.write().await;
+        match redis::cmd("PING").query_async::<_, String>(&mut *conn).await {
+            Ok(_) => ConnectionState::Connected,
+            Err(_) => ConnectionState::Reconnecting,
+        }
     }
 }
@@ -1,56 +1,338 @@
-use anyhow::{Result, Context};
-use crossbeam::queue::ArrayQueue;
-use std::sync::Arc;
+use anyhow::Context;
+use anyhow::Result;
+use shared_memory::ShmemConf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tracing::warn;
 
-/// Shared memory ring buffer for ultra-low latency local IPC
-/// Uses lock-free queue for high throughput
+/// Shared memory ring buffer for ultra-low latency local IPC.
+///
+/// The mapped region is laid out as a fixed header followed by `slot_count`
+/// fixed-size slots:
+///
+/// ```text
+/// [ RingHeader ][ Slot 0 ][ Slot 1 ] ... [ Slot N-1 ]
+/// ```
+///
+/// Each slot carries its own sequence counter and is written using a
+/// single-producer/single-consumer seqlock discipline: the writer bumps the
+/// slot's sequence to an odd value before copying bytes into it, then to the
+/// next even value once the copy is complete. A reader samples the sequence
+/// before and after copying the payload out and retries if the value changed
+/// (the writer raced it) or was odd (the writer is mid-copy), which detects a
+/// torn read without ever blocking the writer.
+///
+/// Every slot is already addressed by its own header carrying a length and a
+/// lock sequence, so (unlike a flat byte-stream ring) a reader can never
+/// observe a torn frame boundary -- there's no byte-chunked accumulation step
+/// to get right. What this ring lacked was backpressure: `write` used to wrap
+/// over unread slots unconditionally. It now tracks the reader's progress in
+/// the shared header and refuses to overwrite slots the reader hasn't caught
+/// up to, returning `ShmError::WouldBlock` instead.
+const MAGIC_VERSION: u64 = (0xB25A_0001u64 << 32) | 1;
+const HEADER_SIZE: usize = 40;
+const SLOT_HEADER_SIZE: usize = 16; // seq: u64, len: u64
+
+#[derive(Debug, thiserror::Error)]
+pub enum ShmError {
+    #[error("message too large for ring slot ({len} > {capacity})")]
+    TooLarge { len: usize, capacity: usize },
+
+    #[error("ring is full: reader hasn't caught up")]
+    WouldBlock,
+}
+
+#[repr(C)]
+struct RingHeader {
+    magic: AtomicU64,
+    slot_size: AtomicU64,
+    slot_count: AtomicU64,
+    write_index: AtomicU64,
+    /// Sequence number (global write index) the reader has fully consumed
+    /// up to. Updated by `read()`, consulted by `write()` to apply
+    /// backpressure instead of lapping an unread slot.
+    read_index: AtomicU64,
+}
+
+impl RingHeader {
+    /// Initializes header fields the first time this process maps the
+    /// segment. Re-attaching to an already-initialized segment is a no-op,
+    /// since the header's layout is fixed at creation time.
+    fn init_if_new(&self, slot_size: u64, slot_count: u64) {
+        if self.magic.load(Ordering::Acquire) == 0 {
+            self.slot_size.store(slot_size, Ordering::Relaxed);
+            self.slot_count.store(slot_count, Ordering::Relaxed);
+            self.write_index.store(0, Ordering::Relaxed);
+            self.read_index.store(0, Ordering::Relaxed);
+            self.magic.store(MAGIC_VERSION, Ordering::Release);
+        }
+    }
+}
+
+#[repr(C)]
+struct SlotHeader {
+    seq: AtomicU64,
+    len: AtomicU64,
+}
+
 pub struct SharedMemoryRing {
     name: String,
-    queue: Arc<ArrayQueue<Vec<u8>>>,
-    max_message_size: usize,
+    _shmem: shared_memory::Shmem,
+    base: *mut u8,
+    slot_size: usize,
+    slot_count: usize,
+    dropped: AtomicU64,
 }
 
+// The mapping is only ever touched through atomics and non-overlapping,
+// length-checked copies, so sharing the raw pointer across threads is sound.
+unsafe impl Send for SharedMemoryRing {}
+unsafe impl Sync for SharedMemoryRing {}
+
 impl SharedMemoryRing {
+    /// Creates (or attaches to) a shared memory ring of roughly `capacity` bytes.
     pub fn new(name: &str, capacity: usize) -> Result<Self> {
-        // For now, use in-memory queue
-        // TODO: Replace with actual shared memory implementation using shared_memory crate
-        let queue = Arc::new(ArrayQueue::new(1024)); // 1024 messages
+        let slot_size = 8 * 1024; // comfortably holds a JSON order book payload
+        let slot_count = (capacity / (slot_size + SLOT_HEADER_SIZE)).max(16);
+        let region_size = HEADER_SIZE + slot_count * (SLOT_HEADER_SIZE + slot_size);
+
+        let shmem = match ShmemConf::new().os_id(name).size(region_size).create() {
+            Ok(shmem) => shmem,
+            Err(shared_memory::ShmemError::MappingIdExists) => ShmemConf::new()
+                .os_id(name)
+                .open()
+                .context("Failed to open existing shared memory mapping")?,
+            Err(e) => return Err(e).context("Failed to create shared memory mapping"),
+        };
 
-        Ok(Self {
+        let base = shmem.as_ptr();
+
+        let ring = Self {
             name: name.to_string(),
-            queue,
-            max_message_size: 64 * 1024, // 64KB max message
-        })
+            _shmem: shmem,
+            base,
+            slot_size,
+            slot_count,
+            dropped: AtomicU64::new(0),
+        };
+
+        ring.header().init_if_new(slot_size as u64, slot_count as u64);
+
+        Ok(ring)
+    }
+
+    fn header(&self) -> &RingHeader {
+        unsafe { &*(self.base as *const RingHeader) }
+    }
+
+    fn slot_header(&self, index: u64) -> &SlotHeader {
+        let offset = HEADER_SIZE + (index as usize) * (SLOT_HEADER_SIZE + self.slot_size);
+        unsafe { &*(self.base.add(offset) as *const SlotHeader) }
+    }
+
+    fn slot_data_ptr(&self, index: u64) -> *mut u8 {
+        let offset =
+            HEADER_SIZE + (index as usize) * (SLOT_HEADER_SIZE + self.slot_size) + SLOT_HEADER_SIZE;
+        unsafe { self.base.add(offset) }
     }
 
-    pub fn write(&self, data: &[u8]) -> Result<()> {
-        if data.len() > self.max_message_size {
-            return Err(anyhow::anyhow!("Message too large"));
+    /// Writes a payload into the next slot, following the seqlock discipline.
+    ///
+    /// Returns the message's sequence number (its global write index) on
+    /// success. Refuses to write -- rather than silently overwriting unread
+    /// data -- once the reader is `slot_count` slots behind; callers should
+    /// treat `ShmError::WouldBlock` as backpressure and retry once the
+    /// consumer has drained more of the ring.
+    pub fn write(&self, data: &[u8]) -> Result<u64, ShmError> {
+        if data.len() > self.slot_size {
+            return Err(ShmError::TooLarge {
+                len: data.len(),
+                capacity: self.slot_size,
+            });
         }
 
-        match self.queue.push(data.to_vec()) {
-            Ok(_) => Ok(()),
-            Err(_) => {
-                warn!("Shared memory ring buffer full, dropping message");
-                Err(anyhow::anyhow!("Ring buffer full"))
-            }
+        let header = self.header();
+        let write_index = header.write_index.load(Ordering::Relaxed);
+        let read_index = header.read_index.load(Ordering::Acquire);
+        if write_index - read_index >= self.slot_count as u64 {
+            return Err(ShmError::WouldBlock);
+        }
+
+        let write_index = header.write_index.fetch_add(1, Ordering::Relaxed);
+        let slot_index = write_index % self.slot_count as u64;
+        let slot = self.slot_header(slot_index);
+
+        // Bump to odd: tells any concurrent reader a write is in flight.
+        let seq = slot.seq.load(Ordering::Relaxed);
+        slot.seq.store(seq.wrapping_add(1), Ordering::Release);
+
+        slot.len.store(data.len() as u64, Ordering::Relaxed);
+        unsafe {
+            std::ptr::copy_nonoverlapping(data.as_ptr(), self.slot_data_ptr(slot_index), data.len());
         }
+
+        // Bump to the next even value: publishes the completed write.
+        slot.seq.store(seq.wrapping_add(2), Ordering::Release);
+
+        Ok(write_index)
     }
 
-    pub fn read(&self) -> Option<Vec<u8>> {
-        self.queue.pop()
+    /// Reads the next unread payload, if any, retrying on torn reads.
+    ///
+    /// Returns the message's sequence number alongside its bytes so callers
+    /// can track their own progress (e.g. for `lag`) independently of the
+    /// shared reader cursor this method advances.
+    pub fn read(&self) -> Option<(u64, Vec<u8>)> {
+        let header = self.header();
+
+        loop {
+            let write_index = header.write_index.load(Ordering::Acquire);
+            let read_index = header.read_index.load(Ordering::Relaxed);
+
+            if read_index >= write_index {
+                return None;
+            }
+
+            // A slow reader detects it has been lapped when the writer is more
+            // than `slot_count` slots ahead: those messages were overwritten.
+            // Under the backpressure check in `write`, this should no longer
+            // happen in steady state, but it's kept as a defensive fallback.
+            if write_index - read_index > self.slot_count as u64 {
+                let dropped = write_index - read_index - self.slot_count as u64;
+                self.dropped.fetch_add(dropped, Ordering::Relaxed);
+                warn!(
+                    ring = %self.name,
+                    dropped,
+                    "reader lapped by writer, skipping ahead"
+                );
+                header
+                    .read_index
+                    .store(write_index - self.slot_count as u64, Ordering::Release);
+                continue;
+            }
+
+            let slot_index = read_index % self.slot_count as u64;
+            let slot = self.slot_header(slot_index);
+
+            let seq_before = slot.seq.load(Ordering::Acquire);
+            if seq_before % 2 != 0 {
+                // Writer is mid-copy on this slot; spin and retry.
+                std::hint::spin_loop();
+                continue;
+            }
+
+            let len = slot.len.load(Ordering::Relaxed) as usize;
+            let mut buf = vec![0u8; len];
+            unsafe {
+                std::ptr::copy_nonoverlapping(self.slot_data_ptr(slot_index), buf.as_mut_ptr(), len);
+            }
+
+            let seq_after = slot.seq.load(Ordering::Acquire);
+            if seq_before != seq_after {
+                // The writer raced us and touched the slot again; retry.
+                continue;
+            }
+
+            header.read_index.store(read_index + 1, Ordering::Release);
+            return Some((read_index, buf));
+        }
     }
 
+    /// Number of unread messages still buffered for this reader.
     pub fn len(&self) -> usize {
-        self.queue.len()
+        let write_index = self.header().write_index.load(Ordering::Acquire);
+        let read_index = self.header().read_index.load(Ordering::Relaxed);
+        write_index.saturating_sub(read_index) as usize
     }
 
     pub fn is_empty(&self) -> bool {
-        self.queue.is_empty()
+        self.len() == 0
+    }
+
+    /// Number of messages written since `reader_seq`, i.e. how far behind a
+    /// consumer that last processed sequence `reader_seq` currently is.
+    pub fn lag(&self, reader_seq: u64) -> u64 {
+        self.header()
+            .write_index
+            .load(Ordering::Acquire)
+            .saturating_sub(reader_seq)
+    }
+
+    /// Total number of messages this reader has lost to writer overruns.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
     }
 }
 
-// TODO: Implement true shared memory using the shared_memory crate
-// This would allow other processes on the same machine to read market data
-// with <1μs latency instead of going through Redis
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Each test maps its own OS shared-memory segment, so give every ring a
+    /// unique name to avoid colliding with other tests running concurrently.
+    fn test_ring(tag: &str) -> SharedMemoryRing {
+        let name = format!(
+            "market-data-shm-test-{}-{}",
+            tag,
+            std::process::id()
+        );
+        SharedMemoryRing::new(&name, 16 * (SLOT_HEADER_SIZE + 8 * 1024)).expect("create ring")
+    }
+
+    #[test]
+    fn test_write_then_read_round_trips_payload_and_sequence() {
+        let ring = test_ring("round-trip");
+
+        let seq = ring.write(b"hello").expect("write");
+        assert_eq!(seq, 0);
+
+        let (read_seq, payload) = ring.read().expect("message available");
+        assert_eq!(read_seq, 0);
+        assert_eq!(payload, b"hello");
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn test_message_too_large_is_rejected() {
+        let ring = test_ring("too-large");
+        let oversized = vec![0u8; ring.slot_size + 1];
+
+        match ring.write(&oversized) {
+            Err(ShmError::TooLarge { len, capacity }) => {
+                assert_eq!(len, oversized.len());
+                assert_eq!(capacity, ring.slot_size);
+            }
+            other => panic!("expected TooLarge, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_write_applies_backpressure_once_reader_falls_behind() {
+        let ring = test_ring("backpressure");
+
+        for i in 0..ring.slot_count {
+            ring.write(format!("msg-{i}").as_bytes()).expect("write within capacity");
+        }
+
+        match ring.write(b"one too many") {
+            Err(ShmError::WouldBlock) => {}
+            other => panic!("expected WouldBlock, got {:?}", other.map(|_| ())),
+        }
+
+        // Draining a single message frees exactly one slot for the writer.
+        ring.read().expect("message available");
+        ring.write(b"now it fits").expect("write after drain");
+    }
+
+    #[test]
+    fn test_lag_reflects_unconsumed_messages() {
+        let ring = test_ring("lag");
+
+        ring.write(b"a").unwrap();
+        ring.write(b"b").unwrap();
+        ring.write(b"c").unwrap();
+
+        assert_eq!(ring.lag(0), 3);
+        let (seq, _) = ring.read().unwrap();
+        assert_eq!(ring.lag(seq + 1), 2);
+    }
+}
@@ -0,0 +1,88 @@
+//! Pluggable publish backends for order book and trade updates.
+//!
+//! `Publisher` always publishes to Redis + shared memory -- that's the
+//! primary, low-latency path every existing consumer (the TUI, the health
+//! routes, local shm readers) depends on, and its reconnect/outbox handling
+//! is tightly coupled to that one connection. `sinks` are additional mirrors
+//! layered on top of it, so an operator can point a second backend (e.g.
+//! NATS JetStream) at the same stream of updates -- to migrate consumers
+//! over gradually, or to get replay-from-sequence durability Redis pub/sub
+//! doesn't offer -- without touching the Redis/shm path at all.
+
+use anyhow::{Context, Result};
+use async_nats::jetstream::{self, Context as JetStreamContext};
+use async_trait::async_trait;
+
+use crate::orderbook::{OrderBook, Trade};
+
+/// A single publish backend for order book and trade updates. `Publisher`
+/// fans every update out to every configured sink; one sink failing doesn't
+/// stop the others from receiving the same update.
+#[async_trait]
+pub trait MarketDataSink: Send + Sync {
+    /// Short, stable label for this sink, used as the `backend` tag on
+    /// `metrics::SINK_PUBLISHES`/`SINK_ERRORS`.
+    fn name(&self) -> &'static str;
+
+    async fn publish_orderbook(&self, book: &OrderBook) -> Result<()>;
+    async fn publish_trade(&self, trade: &Trade) -> Result<()>;
+}
+
+/// Publishes order books and trades into a NATS JetStream stream under
+/// `md.orderbook.{symbol}` / `md.trades.{symbol}`, so a consumer that was
+/// offline can replay from a given sequence number instead of losing
+/// whatever it missed, the way a Redis pub/sub channel would.
+pub struct NatsSink {
+    jetstream: JetStreamContext,
+}
+
+impl NatsSink {
+    /// Connects to `url` and ensures `stream_name` exists, subscribed to
+    /// every `md.>` subject this service publishes.
+    pub async fn connect(url: &str, stream_name: &str) -> Result<Self> {
+        let client = async_nats::connect(url)
+            .await
+            .context("Failed to connect to NATS")?;
+        let jetstream = jetstream::new(client);
+
+        jetstream
+            .get_or_create_stream(jetstream::stream::Config {
+                name: stream_name.to_string(),
+                subjects: vec!["md.>".to_string()],
+                ..Default::default()
+            })
+            .await
+            .context("Failed to create/verify JetStream stream")?;
+
+        Ok(Self { jetstream })
+    }
+
+    async fn publish(&self, subject: String, payload: Vec<u8>) -> Result<()> {
+        self.jetstream
+            .publish(subject, payload.into())
+            .await
+            .context("JetStream publish failed")?
+            .await
+            .context("JetStream publish was not acked")?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MarketDataSink for NatsSink {
+    fn name(&self) -> &'static str {
+        "nats"
+    }
+
+    async fn publish_orderbook(&self, book: &OrderBook) -> Result<()> {
+        let subject = format!("md.orderbook.{}", book.symbol);
+        let payload = serde_json::to_vec(book).context("Failed to serialize order book")?;
+        self.publish(subject, payload).await
+    }
+
+    async fn publish_trade(&self, trade: &Trade) -> Result<()> {
+        let subject = format!("md.trades.{}", trade.symbol);
+        let payload = serde_json::to_vec(trade).context("Failed to serialize trade")?;
+        self.publish(subject, payload).await
+    }
+}
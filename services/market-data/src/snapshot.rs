@@ -1,9 +1,34 @@
 use anyhow::{Result, Context};
+use async_trait::async_trait;
 use serde::Deserialize;
-use tracing::{info, error};
+use tracing::info;
 
 use crate::orderbook::{OrderBook, OrderedFloat};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+fn http_client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .context("Failed to create HTTP client")
+}
+
+fn insert_level(levels: &mut BTreeMap<OrderedFloat, f64>, price: f64, quantity: f64) {
+    if price > 0.0 && quantity > 0.0 {
+        levels.insert(OrderedFloat(price), quantity);
+    }
+}
+
+/// Fetches a REST depth snapshot for `symbol` and normalizes it into the
+/// internal `OrderBook`, so the depth synchronizer - and everything
+/// downstream of it, like the dashboard and health routes - can run
+/// against any venue without caring about that venue's response schema.
+/// Modeled on the pluggable rate-source trait in xmr-btc-swap.
+#[async_trait]
+pub trait SnapshotSource: Send + Sync {
+    async fn fetch_snapshot(&self, symbol: &str, limit: usize) -> Result<OrderBook>;
+}
 
 #[derive(Debug, Deserialize)]
 struct BinanceSnapshot {
@@ -13,17 +38,20 @@ struct BinanceSnapshot {
     asks: Vec<(String, String)>,
 }
 
-pub struct SnapshotFetcher {
+/// Fetches order book snapshots from Binance's `/fapi/v1/depth` REST API.
+pub struct BinanceSource {
     rest_api_url: String,
 }
 
-impl SnapshotFetcher {
+impl BinanceSource {
     pub fn new(rest_api_url: String) -> Self {
         Self { rest_api_url }
     }
+}
 
-    /// Fetch orderbook snapshot from Binance REST API
-    pub async fn fetch_snapshot(&self, symbol: &str, limit: usize) -> Result<OrderBook> {
+#[async_trait]
+impl SnapshotSource for BinanceSource {
+    async fn fetch_snapshot(&self, symbol: &str, limit: usize) -> Result<OrderBook> {
         let url = format!(
             "{}/fapi/v1/depth?symbol={}&limit={}",
             self.rest_api_url, symbol, limit
@@ -31,12 +59,7 @@ impl SnapshotFetcher {
 
         info!("Fetching snapshot for {} from {}", symbol, url);
 
-        // Use reqwest to fetch snapshot
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .build()
-            .context("Failed to create HTTP client")?;
-
+        let client = http_client()?;
         let response = client
             .get(&url)
             .send()
@@ -54,27 +77,23 @@ impl SnapshotFetcher {
             .await
             .context("Failed to parse snapshot response")?;
 
-        // Convert to internal OrderBook format
         let mut orderbook = OrderBook::new(symbol.to_string());
         orderbook.last_update_id = snapshot.last_update_id;
         orderbook.timestamp = chrono::Utc::now().timestamp_micros();
 
-        // Parse bids
-        for (price_str, qty_str) in snapshot.bids {
-            let price: f64 = price_str.parse().unwrap_or(0.0);
-            let qty: f64 = qty_str.parse().unwrap_or(0.0);
-            if price > 0.0 && qty > 0.0 {
-                orderbook.bids.insert(OrderedFloat(price), qty);
-            }
+        for (price, quantity) in snapshot.bids {
+            insert_level(
+                &mut orderbook.bids,
+                price.parse().unwrap_or(0.0),
+                quantity.parse().unwrap_or(0.0),
+            );
         }
-
-        // Parse asks
-        for (price_str, qty_str) in snapshot.asks {
-            let price: f64 = price_str.parse().unwrap_or(0.0);
-            let qty: f64 = qty_str.parse().unwrap_or(0.0);
-            if price > 0.0 && qty > 0.0 {
-                orderbook.asks.insert(OrderedFloat(price), qty);
-            }
+        for (price, quantity) in snapshot.asks {
+            insert_level(
+                &mut orderbook.asks,
+                price.parse().unwrap_or(0.0),
+                quantity.parse().unwrap_or(0.0),
+            );
         }
 
         info!(
@@ -89,14 +108,198 @@ impl SnapshotFetcher {
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct CoinbaseSnapshot {
+    sequence: u64,
+    bids: Vec<(String, String, serde_json::Value)>, // [price, size, num-orders]
+    asks: Vec<(String, String, serde_json::Value)>,
+}
+
+/// Fetches level-2 order book snapshots from Coinbase Exchange's
+/// `/products/{id}/book` REST API.
+pub struct CoinbaseSource {
+    rest_api_url: String,
+}
+
+impl CoinbaseSource {
+    pub fn new(rest_api_url: String) -> Self {
+        Self { rest_api_url }
+    }
+}
+
+#[async_trait]
+impl SnapshotSource for CoinbaseSource {
+    async fn fetch_snapshot(&self, symbol: &str, limit: usize) -> Result<OrderBook> {
+        let url = format!("{}/products/{}/book?level=2", self.rest_api_url, symbol);
+
+        info!("Fetching snapshot for {} from {}", symbol, url);
+
+        let client = http_client()?;
+        let response = client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to send snapshot request")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Snapshot request failed with status {}: {}", status, body);
+        }
+
+        let snapshot: CoinbaseSnapshot = response
+            .json()
+            .await
+            .context("Failed to parse snapshot response")?;
+
+        let mut orderbook = OrderBook::new(symbol.to_string());
+        orderbook.last_update_id = snapshot.sequence;
+        orderbook.timestamp = chrono::Utc::now().timestamp_micros();
+
+        for (price, quantity, _) in snapshot.bids.into_iter().take(limit) {
+            insert_level(
+                &mut orderbook.bids,
+                price.parse().unwrap_or(0.0),
+                quantity.parse().unwrap_or(0.0),
+            );
+        }
+        for (price, quantity, _) in snapshot.asks.into_iter().take(limit) {
+            insert_level(
+                &mut orderbook.asks,
+                price.parse().unwrap_or(0.0),
+                quantity.parse().unwrap_or(0.0),
+            );
+        }
+
+        info!(
+            "Fetched snapshot for {}: {} bids, {} asks, sequence={}",
+            symbol,
+            orderbook.bids.len(),
+            orderbook.asks.len(),
+            orderbook.last_update_id
+        );
+
+        Ok(orderbook)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct KrakenDepth {
+    bids: Vec<(String, String, f64)>, // [price, volume, timestamp]
+    asks: Vec<(String, String, f64)>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KrakenResponse {
+    error: Vec<String>,
+    result: HashMap<String, KrakenDepth>,
+}
+
+/// Fetches order book snapshots from Kraken's `/0/public/Depth` REST API.
+pub struct KrakenSource {
+    rest_api_url: String,
+}
+
+impl KrakenSource {
+    pub fn new(rest_api_url: String) -> Self {
+        Self { rest_api_url }
+    }
+}
+
+#[async_trait]
+impl SnapshotSource for KrakenSource {
+    async fn fetch_snapshot(&self, symbol: &str, limit: usize) -> Result<OrderBook> {
+        let url = format!(
+            "{}/0/public/Depth?pair={}&count={}",
+            self.rest_api_url, symbol, limit
+        );
+
+        info!("Fetching snapshot for {} from {}", symbol, url);
+
+        let client = http_client()?;
+        let response = client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to send snapshot request")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Snapshot request failed with status {}: {}", status, body);
+        }
+
+        let mut snapshot: KrakenResponse = response
+            .json()
+            .await
+            .context("Failed to parse snapshot response")?;
+
+        if !snapshot.error.is_empty() {
+            anyhow::bail!("Kraken snapshot error for {}: {}", symbol, snapshot.error.join(", "));
+        }
+
+        // Kraken keys the result by its own asset-pair name (e.g. "XXBTZUSD"
+        // for "XBTUSD"), which doesn't always match the symbol we asked
+        // for, so fall back to the single entry the response actually has.
+        let depth = snapshot
+            .result
+            .remove(symbol)
+            .or_else(|| snapshot.result.into_values().next())
+            .with_context(|| format!("Kraken snapshot response had no book for {}", symbol))?;
+
+        let mut orderbook = OrderBook::new(symbol.to_string());
+        orderbook.timestamp = chrono::Utc::now().timestamp_micros();
+        // Kraken's depth snapshot carries no update-id/sequence; the
+        // synchronizer only needs last_update_id to be monotonic, and a
+        // freshly fetched snapshot is always treated as authoritative, so
+        // the fetch timestamp is a fine stand-in.
+        orderbook.last_update_id = orderbook.timestamp as u64;
+
+        for (price, quantity, _) in depth.bids {
+            insert_level(
+                &mut orderbook.bids,
+                price.parse().unwrap_or(0.0),
+                quantity.parse().unwrap_or(0.0),
+            );
+        }
+        for (price, quantity, _) in depth.asks {
+            insert_level(
+                &mut orderbook.asks,
+                price.parse().unwrap_or(0.0),
+                quantity.parse().unwrap_or(0.0),
+            );
+        }
+
+        info!(
+            "Fetched snapshot for {}: {} bids, {} asks",
+            symbol,
+            orderbook.bids.len(),
+            orderbook.asks.len(),
+        );
+
+        Ok(orderbook)
+    }
+}
+
+/// Builds the `SnapshotSource` named by `exchange` ("binance", "coinbase",
+/// or "kraken"), pointed at `rest_api_url`.
+pub fn source_for(exchange: &str, rest_api_url: String) -> Result<Arc<dyn SnapshotSource>> {
+    match exchange {
+        "binance" => Ok(Arc::new(BinanceSource::new(rest_api_url))),
+        "coinbase" => Ok(Arc::new(CoinbaseSource::new(rest_api_url))),
+        "kraken" => Ok(Arc::new(KrakenSource::new(rest_api_url))),
+        other => anyhow::bail!("unknown exchange '{}'", other),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[tokio::test]
     async fn test_fetch_snapshot_live() {
-        let fetcher = SnapshotFetcher::new("https://fapi.binance.com".to_string());
-        let result = fetcher.fetch_snapshot("BTCUSDT", 20).await;
+        let source = BinanceSource::new("https://fapi.binance.com".to_string());
+        let result = source.fetch_snapshot("BTCUSDT", 20).await;
 
         assert!(result.is_ok());
         let book = result.unwrap();
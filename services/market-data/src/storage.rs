@@ -0,0 +1,224 @@
+//! Optional TimescaleDB/Postgres persistence for trades and finalized
+//! candles.
+//!
+//! Redis/shm (see `publisher`) is the always-on, ephemeral path every live
+//! consumer reads from -- it has no history once a value is overwritten or
+//! a restart clears shared memory. `StorageWriter` mirrors the same two
+//! event streams into Postgres so historical range queries and candle
+//! backfill-on-restart (feeding `candles::CandleAggregator`) are possible.
+//! Writes are batched on a background task fed by an mpsc channel, so a
+//! slow or unreachable database never stalls the WebSocket processing path:
+//! `record_trade`/`record_candle` are non-blocking and drop the event
+//! (counted in `metrics::DB_ERRORS`) rather than apply backpressure.
+
+use anyhow::{Context, Result};
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use tokio::sync::mpsc;
+use tokio::time::{interval, Duration};
+
+use crate::candles::Candle;
+use crate::metrics;
+use crate::orderbook::Trade;
+
+const CHANNEL_CAPACITY: usize = 8192;
+const FLUSH_INTERVAL: Duration = Duration::from_millis(500);
+
+enum StorageEvent {
+    Trade(Trade),
+    Candle(Candle),
+}
+
+#[derive(Clone)]
+pub struct StorageWriter {
+    tx: mpsc::Sender<StorageEvent>,
+}
+
+impl StorageWriter {
+    /// Connects to `database_url`, creates the `trades`/`candles` tables if
+    /// missing, and spawns the background batching writer. `batch_size`
+    /// bounds both the channel-driven and timer-driven flush.
+    pub async fn connect(database_url: &str, batch_size: usize) -> Result<Self> {
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(database_url)
+            .await
+            .context("Failed to connect to Postgres")?;
+
+        ensure_schema(&pool).await?;
+
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        tokio::spawn(run_writer(pool, rx, batch_size));
+
+        Ok(Self { tx })
+    }
+
+    /// Queues `trade` for a batched insert. Non-blocking: if the writer is
+    /// falling behind and the channel is full, the trade is dropped rather
+    /// than stalling the caller.
+    pub fn record_trade(&self, trade: Trade) {
+        if self.tx.try_send(StorageEvent::Trade(trade)).is_err() {
+            metrics::DB_ERRORS.with_label_values(&["trade"]).inc();
+        }
+    }
+
+    /// Queues a finalized `candle` for a batched insert, same non-blocking
+    /// contract as `record_trade`.
+    pub fn record_candle(&self, candle: Candle) {
+        if self.tx.try_send(StorageEvent::Candle(candle)).is_err() {
+            metrics::DB_ERRORS.with_label_values(&["candle"]).inc();
+        }
+    }
+}
+
+async fn ensure_schema(pool: &PgPool) -> Result<()> {
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS trades (
+            symbol TEXT NOT NULL,
+            trade_id BIGINT NOT NULL,
+            price DOUBLE PRECISION NOT NULL,
+            quantity DOUBLE PRECISION NOT NULL,
+            side TEXT NOT NULL,
+            event_time TIMESTAMPTZ NOT NULL,
+            ingest_time TIMESTAMPTZ NOT NULL DEFAULT now(),
+            PRIMARY KEY (symbol, trade_id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create trades table")?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS candles (
+            symbol TEXT NOT NULL,
+            resolution TEXT NOT NULL,
+            open_time BIGINT NOT NULL,
+            open DOUBLE PRECISION NOT NULL,
+            high DOUBLE PRECISION NOT NULL,
+            low DOUBLE PRECISION NOT NULL,
+            close DOUBLE PRECISION NOT NULL,
+            base_volume DOUBLE PRECISION NOT NULL,
+            quote_volume DOUBLE PRECISION NOT NULL,
+            trade_count BIGINT NOT NULL,
+            ingest_time TIMESTAMPTZ NOT NULL DEFAULT now(),
+            PRIMARY KEY (symbol, resolution, open_time)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create candles table")?;
+
+    Ok(())
+}
+
+async fn run_writer(pool: PgPool, mut rx: mpsc::Receiver<StorageEvent>, batch_size: usize) {
+    let mut trades = Vec::with_capacity(batch_size);
+    let mut candles = Vec::with_capacity(batch_size);
+    let mut ticker = interval(FLUSH_INTERVAL);
+
+    loop {
+        tokio::select! {
+            event = rx.recv() => {
+                match event {
+                    Some(StorageEvent::Trade(trade)) => trades.push(trade),
+                    Some(StorageEvent::Candle(candle)) => candles.push(candle),
+                    None => {
+                        flush(&pool, &mut trades, &mut candles).await;
+                        return;
+                    }
+                }
+                if trades.len() >= batch_size || candles.len() >= batch_size {
+                    flush(&pool, &mut trades, &mut candles).await;
+                }
+            }
+            _ = ticker.tick() => {
+                flush(&pool, &mut trades, &mut candles).await;
+            }
+        }
+    }
+}
+
+async fn flush(pool: &PgPool, trades: &mut Vec<Trade>, candles: &mut Vec<Candle>) {
+    if !trades.is_empty() {
+        match insert_trades(pool, trades).await {
+            Ok(n) => metrics::DB_WRITES.with_label_values(&["trade"]).inc_by(n as f64),
+            Err(e) => {
+                tracing::error!("Failed to batch-insert trades: {}", e);
+                metrics::DB_ERRORS.with_label_values(&["trade"]).inc();
+            }
+        }
+        trades.clear();
+    }
+
+    if !candles.is_empty() {
+        match insert_candles(pool, candles).await {
+            Ok(n) => metrics::DB_WRITES.with_label_values(&["candle"]).inc_by(n as f64),
+            Err(e) => {
+                tracing::error!("Failed to batch-insert candles: {}", e);
+                metrics::DB_ERRORS.with_label_values(&["candle"]).inc();
+            }
+        }
+        candles.clear();
+    }
+}
+
+async fn insert_trades(pool: &PgPool, trades: &[Trade]) -> Result<usize> {
+    let mut tx = pool.begin().await?;
+    for trade in trades {
+        sqlx::query(
+            r#"
+            INSERT INTO trades (symbol, trade_id, price, quantity, side, event_time)
+            VALUES ($1, $2, $3, $4, $5, to_timestamp($6::double precision / 1000.0))
+            ON CONFLICT (symbol, trade_id) DO NOTHING
+            "#,
+        )
+        .bind(&trade.symbol)
+        .bind(trade.trade_id as i64)
+        .bind(trade.price.to_f64().unwrap_or(0.0))
+        .bind(trade.quantity.to_f64().unwrap_or(0.0))
+        .bind(if trade.is_buyer_maker { "sell" } else { "buy" })
+        .bind(trade.timestamp)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(trades.len())
+}
+
+async fn insert_candles(pool: &PgPool, candles: &[Candle]) -> Result<usize> {
+    let mut tx = pool.begin().await?;
+    for candle in candles {
+        sqlx::query(
+            r#"
+            INSERT INTO candles
+                (symbol, resolution, open_time, open, high, low, close, base_volume, quote_volume, trade_count)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (symbol, resolution, open_time) DO UPDATE SET
+                open = EXCLUDED.open,
+                high = EXCLUDED.high,
+                low = EXCLUDED.low,
+                close = EXCLUDED.close,
+                base_volume = EXCLUDED.base_volume,
+                quote_volume = EXCLUDED.quote_volume,
+                trade_count = EXCLUDED.trade_count
+            "#,
+        )
+        .bind(&candle.symbol)
+        .bind(&candle.resolution)
+        .bind(candle.open_time)
+        .bind(candle.open)
+        .bind(candle.high)
+        .bind(candle.low)
+        .bind(candle.close)
+        .bind(candle.base_volume)
+        .bind(candle.quote_volume)
+        .bind(candle.trade_count as i64)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(candles.len())
+}
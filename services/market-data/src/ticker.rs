@@ -0,0 +1,127 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::RwLock;
+
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::orderbook::Trade;
+
+/// Volume/high/low over a trailing window of trades for one symbol.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WindowStats {
+    pub volume: f64,
+    pub high: f64,
+    pub low: f64,
+}
+
+/// Rolls a `Trade` stream into trailing-window volume/high/low per symbol,
+/// keyed by symbol alone (unlike `CandleAggregator`, which also buckets by
+/// resolution). Each symbol keeps a `VecDeque<(timestamp_ms, price, size)>`
+/// ordered oldest-first; every trade evicts entries older than
+/// `now - window`, and `volume`, `high` and `low` are then recomputed over
+/// whatever remains in the window. No running sum is kept.
+pub struct RollingWindow {
+    entries: RwLock<HashMap<String, VecDeque<(i64, f64, f64)>>>,
+    window_ms: i64,
+}
+
+impl RollingWindow {
+    pub fn new(window_secs: u64) -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            window_ms: window_secs as i64 * 1000,
+        }
+    }
+
+    /// Folds `trade` into its symbol's window, evicting entries that have
+    /// aged out, and returns the window's volume/high/low after the update.
+    pub fn record_trade(&self, trade: &Trade) -> WindowStats {
+        let price = trade.price.to_f64().unwrap_or(0.0);
+        let size = trade.quantity.to_f64().unwrap_or(0.0);
+
+        let mut entries = self.entries.write().unwrap();
+        let deque = entries.entry(trade.symbol.clone()).or_default();
+
+        deque.push_back((trade.timestamp, price, size));
+        let cutoff = trade.timestamp - self.window_ms;
+        while matches!(deque.front(), Some((ts, _, _)) if *ts < cutoff) {
+            deque.pop_front();
+        }
+
+        Self::fold(deque)
+    }
+
+    /// Current volume/high/low for `symbol` as of its last recorded trade,
+    /// for read sites (e.g. `publish_orderbook`) that don't themselves have
+    /// a trade to fold in. Stale entries are evicted lazily on the next
+    /// `record_trade` rather than here.
+    pub fn current(&self, symbol: &str) -> WindowStats {
+        self.entries
+            .read()
+            .unwrap()
+            .get(symbol)
+            .map(Self::fold)
+            .unwrap_or_default()
+    }
+
+    fn fold(deque: &VecDeque<(i64, f64, f64)>) -> WindowStats {
+        let mut stats = WindowStats::default();
+        let mut first = true;
+        for (_, price, size) in deque {
+            stats.volume += size;
+            if first {
+                stats.high = *price;
+                stats.low = *price;
+                first = false;
+            } else {
+                stats.high = stats.high.max(*price);
+                stats.low = stats.low.min(*price);
+            }
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(symbol: &str, price: f64, quantity: f64, timestamp: i64) -> Trade {
+        Trade::new(symbol, 1, price, quantity, timestamp, false)
+    }
+
+    #[test]
+    fn test_accumulates_volume_and_high_low_within_window() {
+        let window = RollingWindow::new(60);
+
+        window.record_trade(&trade("BTCUSDT", 100.0, 1.0, 0));
+        window.record_trade(&trade("BTCUSDT", 110.0, 2.0, 10_000));
+        let stats = window.record_trade(&trade("BTCUSDT", 90.0, 1.0, 20_000));
+
+        assert_eq!(stats.volume, 4.0);
+        assert_eq!(stats.high, 110.0);
+        assert_eq!(stats.low, 90.0);
+    }
+
+    #[test]
+    fn test_evicts_entries_older_than_window() {
+        let window = RollingWindow::new(60);
+
+        window.record_trade(&trade("BTCUSDT", 100.0, 1.0, 0));
+        let stats = window.record_trade(&trade("BTCUSDT", 200.0, 1.0, 61_000));
+
+        assert_eq!(stats.volume, 1.0);
+        assert_eq!(stats.high, 200.0);
+        assert_eq!(stats.low, 200.0);
+    }
+
+    #[test]
+    fn test_windows_are_tracked_independently_per_symbol() {
+        let window = RollingWindow::new(60);
+
+        window.record_trade(&trade("BTCUSDT", 100.0, 1.0, 0));
+        let stats = window.record_trade(&trade("ETHUSDT", 10.0, 5.0, 0));
+
+        assert_eq!(stats.volume, 5.0);
+        assert_eq!(stats.high, 10.0);
+    }
+}
@@ -0,0 +1,477 @@
+//! Authenticated Binance user-data-stream client: account/order events,
+//! published alongside the public market-data streams so downstream
+//! consumers (the TUI's positions/alerts panels, among others) see live
+//! account state instead of externally-injected data.
+//!
+//! Binance's user-data stream is keyed off a `listenKey` obtained over REST
+//! and fed into a plain `wss://.../ws/<listenKey>` connection; the key
+//! expires after 60 minutes unless kept alive, so this client refreshes it
+//! on a 30-minute timer (and immediately on a `listenKeyExpired` event) and
+//! reconnects, mirroring `WebSocketClient`'s own reconnect/backoff loop.
+
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use reqwest::Client as HttpClient;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, info, warn};
+
+use crate::publisher::Publisher;
+use crate::metrics;
+
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub level: AlertLevel,
+    pub message: String,
+    pub symbol: Option<String>,
+    pub timestamp: i64,
+}
+
+impl Serialize for AlertLevel {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let s = match self {
+            AlertLevel::Info => "info",
+            AlertLevel::Warning => "warning",
+            AlertLevel::Error => "error",
+        };
+        serializer.serialize_str(s)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Position {
+    pub symbol: String,
+    pub position_amount: f64,
+    pub entry_price: f64,
+    pub unrealized_pnl: f64,
+    pub update_time: i64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FillSide {
+    Buy,
+    Sell,
+}
+
+/// A single execution against a resting or aggressing order, in the unified
+/// schema `Publisher::publish_fill` emits. Normalizes exchange-native
+/// strings/fixed-point fields into `Decimal` at the boundary so every
+/// downstream consumer (TUI fills panel, journaling, PnL) sees one
+/// consistent representation rather than re-parsing Binance's wire types.
+#[derive(Debug, Clone, Serialize)]
+pub struct Fill {
+    pub symbol: String,
+    pub side: FillSide,
+    pub price: rust_decimal::Decimal,
+    pub size: rust_decimal::Decimal,
+    pub fee: rust_decimal::Decimal,
+    pub fee_asset: String,
+    pub pnl: rust_decimal::Decimal,
+    pub is_maker: bool,
+    pub order_id: String,
+    pub trade_id: String,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "e")]
+enum UserDataMessage {
+    #[serde(rename = "ORDER_TRADE_UPDATE")]
+    OrderTradeUpdate {
+        #[serde(rename = "E")]
+        event_time: i64,
+        #[serde(rename = "T")]
+        transaction_time: i64,
+        #[serde(rename = "o")]
+        order: OrderUpdate,
+    },
+    #[serde(rename = "executionReport")]
+    ExecutionReport {
+        #[serde(rename = "E")]
+        event_time: i64,
+        #[serde(rename = "s")]
+        symbol: String,
+        #[serde(rename = "X")]
+        order_status: String,
+        #[serde(rename = "r")]
+        reject_reason: String,
+    },
+    #[serde(rename = "ACCOUNT_UPDATE")]
+    AccountUpdate {
+        #[serde(rename = "E")]
+        event_time: i64,
+        #[serde(rename = "a")]
+        update: AccountUpdateData,
+    },
+    #[serde(rename = "balanceUpdate")]
+    BalanceUpdate {
+        #[serde(rename = "E")]
+        event_time: i64,
+        #[serde(rename = "a")]
+        asset: String,
+        #[serde(rename = "d")]
+        delta: String,
+    },
+    #[serde(rename = "listenKeyExpired")]
+    ListenKeyExpired {
+        #[serde(rename = "E")]
+        event_time: i64,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct OrderUpdate {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "X")]
+    order_status: String,
+    #[serde(rename = "r")]
+    reject_reason: String,
+    #[serde(rename = "x")]
+    execution_type: String,
+    #[serde(rename = "i")]
+    order_id: u64,
+    #[serde(rename = "S")]
+    side: String,
+    #[serde(rename = "L")]
+    last_filled_price: String,
+    #[serde(rename = "l")]
+    last_filled_qty: String,
+    #[serde(rename = "N", default)]
+    commission_asset: Option<String>,
+    #[serde(rename = "n", default)]
+    commission: Option<String>,
+    #[serde(rename = "t")]
+    trade_id: i64,
+    #[serde(rename = "m")]
+    is_maker: bool,
+    #[serde(rename = "rp", default)]
+    realized_pnl: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccountUpdateData {
+    #[serde(rename = "P")]
+    positions: Vec<AccountPosition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccountPosition {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "pa")]
+    position_amount: String,
+    #[serde(rename = "ep")]
+    entry_price: String,
+    #[serde(rename = "up")]
+    unrealized_pnl: String,
+}
+
+pub struct UserDataClient {
+    rest_api_url: String,
+    ws_base_url: String,
+    api_key: String,
+    publisher: Arc<Publisher>,
+    reconnect_delay: Duration,
+    max_reconnect_delay: Duration,
+}
+
+impl UserDataClient {
+    pub fn new(rest_api_url: String, ws_base_url: String, api_key: String, publisher: Arc<Publisher>) -> Self {
+        Self {
+            rest_api_url,
+            ws_base_url,
+            api_key,
+            publisher,
+            reconnect_delay: Duration::from_millis(1000),
+            max_reconnect_delay: Duration::from_secs(60),
+        }
+    }
+
+    pub async fn connect_and_run(&self) -> Result<()> {
+        let mut current_delay = self.reconnect_delay;
+
+        loop {
+            match self.run_session().await {
+                Ok(_) => {
+                    info!("User data stream session ended, reconnecting");
+                    current_delay = self.reconnect_delay;
+                }
+                Err(e) => {
+                    warn!("User data stream error: {}", e);
+                    sleep(current_delay).await;
+                    current_delay = std::cmp::min(current_delay * 2, self.max_reconnect_delay);
+                }
+            }
+        }
+    }
+
+    /// Obtains a fresh `listenKey`, connects, and processes events until the
+    /// key needs a keep-alive refresh, a `listenKeyExpired` event arrives,
+    /// or the socket drops.
+    async fn run_session(&self) -> Result<()> {
+        let listen_key = self.obtain_listen_key().await?;
+        let url = format!("{}/ws/{}", self.ws_base_url, listen_key);
+
+        info!("Connecting to user data stream at {}", url);
+        let (mut write, mut read) = connect_async(&url)
+            .await
+            .context("Failed to connect to user data stream")?
+            .0
+            .split();
+
+        // Send pings periodically, same pattern as `WebSocketClient`.
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(30));
+            loop {
+                interval.tick().await;
+                if write.send(Message::Ping(vec![])).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut keep_alive = tokio::time::interval(KEEP_ALIVE_INTERVAL);
+        keep_alive.tick().await; // first tick fires immediately; consume it
+
+        loop {
+            tokio::select! {
+                msg = read.next() => {
+                    let Some(msg) = msg else { break };
+                    let msg = msg.context("User data stream message error")?;
+                    match msg {
+                        Message::Text(text) => {
+                            match self.process_message(&text).await {
+                                Ok(expired) if expired => {
+                                    self.refresh_listen_key(&listen_key).await?;
+                                    return Ok(());
+                                }
+                                Ok(_) => {}
+                                Err(e) => {
+                                    debug!("Error processing user data message: {}", e);
+                                    metrics::MESSAGES_ERROR.with_label_values(&["user_data"]).inc();
+                                }
+                            }
+                        }
+                        Message::Close(_) => break,
+                        _ => {}
+                    }
+                }
+                _ = keep_alive.tick() => {
+                    self.refresh_listen_key(&listen_key).await?;
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Processes one frame. Returns `Ok(true)` if this was a
+    /// `listenKeyExpired` event, signalling the caller to refresh and
+    /// reconnect rather than keep reading this socket.
+    async fn process_message(&self, text: &str) -> Result<bool> {
+        let message: UserDataMessage =
+            serde_json::from_str(text).context("Failed to parse user data message")?;
+
+        match message {
+            UserDataMessage::OrderTradeUpdate { transaction_time, order, .. } => {
+                if order.execution_type == "TRADE" {
+                    self.handle_fill(&order, transaction_time).await;
+                }
+                self.handle_order_event(&order.symbol, &order.order_status, &order.reject_reason, transaction_time)
+                    .await;
+            }
+            UserDataMessage::ExecutionReport { event_time, symbol, order_status, reject_reason } => {
+                self.handle_order_event(&symbol, &order_status, &reject_reason, event_time).await;
+            }
+            UserDataMessage::AccountUpdate { event_time, update } => {
+                for position in update.positions {
+                    let position = Position {
+                        symbol: position.symbol,
+                        position_amount: position.position_amount.parse().unwrap_or(0.0),
+                        entry_price: position.entry_price.parse().unwrap_or(0.0),
+                        unrealized_pnl: position.unrealized_pnl.parse().unwrap_or(0.0),
+                        update_time: event_time,
+                    };
+                    if let Err(e) = self.publisher.publish_position(&position).await {
+                        warn!("Failed to publish position update: {}", e);
+                    }
+                }
+            }
+            UserDataMessage::BalanceUpdate { asset, delta, .. } => {
+                debug!("Balance update: {} {}", asset, delta);
+            }
+            UserDataMessage::ListenKeyExpired { .. } => {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Builds and publishes a `Fill` for an `ORDER_TRADE_UPDATE` whose
+    /// execution type is `TRADE`, i.e. this update represents an actual
+    /// execution rather than a pure status transition.
+    async fn handle_fill(&self, order: &OrderUpdate, timestamp: i64) {
+        use rust_decimal::Decimal;
+        use std::str::FromStr;
+
+        let side = if order.side == "BUY" { FillSide::Buy } else { FillSide::Sell };
+        let fill = Fill {
+            symbol: order.symbol.clone(),
+            side,
+            price: Decimal::from_str(&order.last_filled_price).unwrap_or_default(),
+            size: Decimal::from_str(&order.last_filled_qty).unwrap_or_default(),
+            fee: order
+                .commission
+                .as_deref()
+                .and_then(|c| Decimal::from_str(c).ok())
+                .unwrap_or_default(),
+            fee_asset: order.commission_asset.clone().unwrap_or_default(),
+            pnl: order
+                .realized_pnl
+                .as_deref()
+                .and_then(|p| Decimal::from_str(p).ok())
+                .unwrap_or_default(),
+            is_maker: order.is_maker,
+            order_id: order.order_id.to_string(),
+            trade_id: order.trade_id.to_string(),
+            timestamp,
+        };
+
+        if let Err(e) = self.publisher.publish_fill(&fill).await {
+            warn!("Failed to publish fill: {}", e);
+        }
+    }
+
+    async fn handle_order_event(&self, symbol: &str, order_status: &str, reject_reason: &str, timestamp: i64) {
+        let Some(level) = alert_level_for_order_status(order_status, reject_reason) else {
+            return;
+        };
+
+        let alert = Alert {
+            level,
+            message: format!("Order {} for {} ({})", order_status, symbol, reject_reason),
+            symbol: Some(symbol.to_string()),
+            timestamp,
+        };
+
+        if let Err(e) = self.publisher.publish_alert(&alert).await {
+            warn!("Failed to publish alert: {}", e);
+        }
+    }
+
+}
+
+/// Maps an order's status (and, for `NEW`, its reject reason) to the alert
+/// level that should be raised, or `None` if the event doesn't warrant one.
+fn alert_level_for_order_status(order_status: &str, reject_reason: &str) -> Option<AlertLevel> {
+    match order_status {
+        "REJECTED" | "EXPIRED" => Some(AlertLevel::Error),
+        "NEW" if reject_reason != "NONE" => Some(AlertLevel::Error),
+        _ => None,
+    }
+}
+
+impl UserDataClient {
+    async fn obtain_listen_key(&self) -> Result<String> {
+        #[derive(Deserialize)]
+        struct ListenKeyResponse {
+            #[serde(rename = "listenKey")]
+            listen_key: String,
+        }
+
+        let url = format!("{}/fapi/v1/listenKey", self.rest_api_url);
+        let response: ListenKeyResponse = HttpClient::new()
+            .post(&url)
+            .header("X-MBX-APIKEY", &self.api_key)
+            .send()
+            .await
+            .context("Failed to request listen key")?
+            .json()
+            .await
+            .context("Failed to parse listen key response")?;
+
+        Ok(response.listen_key)
+    }
+
+    async fn refresh_listen_key(&self, listen_key: &str) -> Result<()> {
+        let url = format!("{}/fapi/v1/listenKey", self.rest_api_url);
+        HttpClient::new()
+            .put(&url)
+            .header("X-MBX-APIKEY", &self.api_key)
+            .query(&[("listenKey", listen_key)])
+            .send()
+            .await
+            .context("Failed to refresh listen key")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_account_update_into_positions() {
+        let text = r#"{
+            "e": "ACCOUNT_UPDATE",
+            "E": 1700000000000,
+            "a": {
+                "P": [
+                    {"s": "BTCUSDT", "pa": "0.500", "ep": "42000.5", "up": "12.3"}
+                ]
+            }
+        }"#;
+
+        let message: UserDataMessage = serde_json::from_str(text).unwrap();
+        match message {
+            UserDataMessage::AccountUpdate { event_time, update } => {
+                assert_eq!(event_time, 1700000000000);
+                assert_eq!(update.positions.len(), 1);
+                assert_eq!(update.positions[0].symbol, "BTCUSDT");
+                assert_eq!(update.positions[0].position_amount, "0.500");
+            }
+            other => panic!("expected AccountUpdate, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parses_listen_key_expired() {
+        let text = r#"{"e": "listenKeyExpired", "E": 1700000000000}"#;
+        let message: UserDataMessage = serde_json::from_str(text).unwrap();
+        assert!(matches!(message, UserDataMessage::ListenKeyExpired { .. }));
+    }
+
+    #[test]
+    fn test_alert_level_serializes_as_lowercase_string() {
+        let json = serde_json::to_string(&AlertLevel::Error).unwrap();
+        assert_eq!(json, "\"error\"");
+    }
+
+    #[test]
+    fn test_rejected_order_status_is_flagged_as_error_level_alert() {
+        assert_eq!(
+            alert_level_for_order_status("REJECTED", "NONE"),
+            Some(AlertLevel::Error)
+        );
+        assert_eq!(alert_level_for_order_status("FILLED", "NONE"), None);
+    }
+}
@@ -1,83 +1,63 @@
 use anyhow::{Result, Context};
 use futures_util::{SinkExt, StreamExt};
-use serde::Deserialize;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
 use tokio::time::sleep;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
 use tracing::{debug, info, warn};
 
-use crate::orderbook::{DepthUpdate, OrderBookManager, PriceLevel, Trade};
+use crate::depth_sync::{BookUpdate, DepthSynchronizer};
+use crate::exchange::{BookTicker, ExchangeAdapter, ExchangeMessage, Kline};
+use crate::orderbook::{DepthUpdate, OrderBookManager, Trade};
 use crate::publisher::Publisher;
-use crate::snapshot::SnapshotFetcher;
+use crate::snapshot::SnapshotSource;
+use crate::ws_server::DashboardServer;
 use crate::metrics;
 
+/// Drives a single-symbol WebSocket connection: connects, reconnects with
+/// backoff on error, and feeds every parsed message to the depth
+/// synchronizer and publisher. All venue-specific framing (URL shape,
+/// subscription handshake, wire format, liveness signal) lives behind
+/// `adapter` rather than in this loop.
 pub struct WebSocketClient {
     symbol: String,
     ws_url: String,
     orderbook_manager: Arc<OrderBookManager>,
     publisher: Arc<Publisher>,
-    _snapshot_fetcher: Arc<SnapshotFetcher>,
+    snapshot_fetcher: Arc<dyn SnapshotSource>,
     order_book_depth: usize,
+    dashboard_server: Arc<DashboardServer>,
+    kline_interval: String,
+    adapter: Arc<dyn ExchangeAdapter>,
     reconnect_delay: Duration,
     max_reconnect_delay: Duration,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(tag = "e")]
-enum BinanceMessage {
-    #[serde(rename = "depthUpdate")]
-    DepthUpdate(BinanceDepthUpdate),
-    #[serde(rename = "aggTrade")]
-    AggTrade(BinanceAggTrade),
-}
-
-#[derive(Debug, Deserialize)]
-struct BinanceDepthUpdate {
-    #[serde(rename = "s")]
-    symbol: String,
-    #[serde(rename = "U")]
-    first_update_id: u64,
-    #[serde(rename = "u")]
-    last_update_id: u64,
-    #[serde(rename = "b")]
-    bids: Vec<(String, String)>, // [price, quantity]
-    #[serde(rename = "a")]
-    asks: Vec<(String, String)>,
-}
-
-#[derive(Debug, Deserialize)]
-struct BinanceAggTrade {
-    #[serde(rename = "s")]
-    symbol: String,
-    #[serde(rename = "a")]
-    trade_id: u64,
-    #[serde(rename = "p")]
-    price: String,
-    #[serde(rename = "q")]
-    quantity: String,
-    #[serde(rename = "T")]
-    timestamp: i64,
-    #[serde(rename = "m")]
-    is_buyer_maker: bool,
-}
-
 impl WebSocketClient {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         symbol: String,
         ws_url: String,
         orderbook_manager: Arc<OrderBookManager>,
         publisher: Arc<Publisher>,
-        snapshot_fetcher: Arc<SnapshotFetcher>,
+        snapshot_fetcher: Arc<dyn SnapshotSource>,
         order_book_depth: usize,
+        dashboard_server: Arc<DashboardServer>,
+        kline_interval: String,
+        adapter: Arc<dyn ExchangeAdapter>,
     ) -> Self {
         Self {
             symbol,
             ws_url,
             orderbook_manager,
             publisher,
-            _snapshot_fetcher: snapshot_fetcher,
+            snapshot_fetcher,
             order_book_depth,
+            dashboard_server,
+            kline_interval,
+            adapter,
             reconnect_delay: Duration::from_millis(1000),
             max_reconnect_delay: Duration::from_secs(60),
         }
@@ -109,16 +89,15 @@ impl WebSocketClient {
     }
 
     async fn run_connection(&self) -> Result<()> {
-        // Skip REST snapshot fetch (geo-blocked) - build orderbook from WebSocket
-        info!("Building orderbook for {} from WebSocket updates (REST API geo-blocked)", self.symbol);
-
-        // Connect to WebSocket for incremental updates
-        let streams = format!(
-            "{}@depth@100ms/{}@aggTrade",
-            self.symbol.to_lowercase(),
-            self.symbol.to_lowercase()
+        info!(
+            "Synchronizing orderbook for {} from REST snapshot + {} WebSocket diffs",
+            self.symbol,
+            self.adapter.name()
         );
-        let url = format!("{}?streams={}", self.ws_url, streams);
+
+        let url = self
+            .adapter
+            .subscribe_url(&self.ws_url, &self.symbol, &self.kline_interval);
 
         info!("Connecting to {} for {}", url, self.symbol);
 
@@ -131,146 +110,162 @@ impl WebSocketClient {
 
         let (mut write, mut read) = ws_stream.split();
 
-        // Send ping periodically
-        let symbol_clone = self.symbol.clone();
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(30));
-            loop {
-                interval.tick().await;
-                if write.send(Message::Ping(vec![])).await.is_err() {
-                    warn!("Failed to send ping for {}", symbol_clone);
-                    break;
+        for message in self
+            .adapter
+            .subscribe_messages(&self.symbol, &self.kline_interval)
+        {
+            write
+                .send(message)
+                .await
+                .context("Failed to send subscribe message")?;
+        }
+
+        // Send a keep-alive ping on venues that need one (Binance); venues
+        // that push their own heartbeat frames (Kraken) rely on
+        // `heartbeat_timeout` below instead, so `write` is simply left
+        // unused and the connection's write half stays open.
+        if let Some(ping_msg) = self.adapter.outbound_ping() {
+            let symbol_clone = self.symbol.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(Duration::from_secs(30));
+                loop {
+                    interval.tick().await;
+                    if write.send(ping_msg.clone()).await.is_err() {
+                        warn!("Failed to send ping for {}", symbol_clone);
+                        break;
+                    }
                 }
+            });
+        }
+
+        // The depth synchronizer owns the canonical book for this connection:
+        // it buffers diff events against a REST snapshot and keeps the book
+        // gap-free, emitting a refreshed copy after every applied event.
+        let (depth_tx, depth_rx) = mpsc::channel::<DepthUpdate>(256);
+        let (update_tx, mut update_rx) = mpsc::channel::<BookUpdate>(16);
+        let synchronizer =
+            DepthSynchronizer::new(self.symbol.clone(), Arc::clone(&self.snapshot_fetcher));
+        let sync_symbol = self.symbol.clone();
+        tokio::spawn(async move {
+            if let Err(e) = synchronizer.run(depth_rx, update_tx).await {
+                warn!("Depth synchronizer for {} exited: {}", sync_symbol, e);
             }
         });
 
+        let mut last_alive = Instant::now();
+        let heartbeat_timeout = self.adapter.heartbeat_timeout();
+        let mut liveness_check = tokio::time::interval(Duration::from_secs(5));
+
         // Process messages
-        while let Some(msg) = read.next().await {
-            let msg = msg.context("WebSocket message error")?;
-
-            match msg {
-                Message::Text(text) => {
-                    if let Err(e) = self.process_message(&text).await {
-                        debug!("Error processing message: {}", e);
-                        metrics::MESSAGES_ERROR
+        loop {
+            tokio::select! {
+                msg = read.next() => {
+                    let Some(msg) = msg else { break };
+                    let msg = msg.context("WebSocket message error")?;
+
+                    match msg {
+                        Message::Text(text) => {
+                            last_alive = Instant::now();
+                            if let Err(e) = self.process_message(&text, &depth_tx).await {
+                                debug!("Error processing message: {}", e);
+                                metrics::MESSAGES_ERROR
+                                    .with_label_values(&[&self.symbol])
+                                    .inc();
+                            }
+                        }
+                        Message::Pong(_) => {
+                            debug!("Received pong for {}", self.symbol);
+                            last_alive = Instant::now();
+                        }
+                        Message::Close(_) => {
+                            info!("WebSocket closed for {}", self.symbol);
+                            metrics::WS_CONNECTED.with_label_values(&[&self.symbol]).set(0.0);
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                Some(update) = update_rx.recv() => {
+                    let (book, resynced) = match update {
+                        BookUpdate::Applied(book) => (book, false),
+                        BookUpdate::Resynced(book) => (book, true),
+                    };
+
+                    // Only publish if we have meaningful data
+                    if book.bids.len() > 0 && book.asks.len() > 0 {
+                        metrics::ORDERBOOK_UPDATES
                             .with_label_values(&[&self.symbol])
                             .inc();
+
+                        let previous = self.orderbook_manager.get(&self.symbol);
+                        self.orderbook_manager.set(&self.symbol, book.clone());
+                        if resynced {
+                            self.dashboard_server.broadcast_checkpoint(&self.symbol, &book).await;
+                        } else {
+                            self.dashboard_server
+                                .broadcast_update(&self.symbol, previous.as_ref(), &book)
+                                .await;
+                        }
+
+                        // Publish to Redis and shared memory
+                        self.publisher
+                            .publish_orderbook(&book)
+                            .await
+                            .context("Failed to publish orderbook")?;
+
+                        debug!(
+                            "Updated order book for {}: {} bids, {} asks, last_update_id={}",
+                            self.symbol,
+                            book.bids.len(),
+                            book.asks.len(),
+                            book.last_update_id
+                        );
                     }
                 }
-                Message::Pong(_) => {
-                    debug!("Received pong for {}", self.symbol);
-                }
-                Message::Close(_) => {
-                    info!("WebSocket closed for {}", self.symbol);
-                    metrics::WS_CONNECTED.with_label_values(&[&self.symbol]).set(0.0);
-                    break;
+                _ = liveness_check.tick() => {
+                    if last_alive.elapsed() > heartbeat_timeout {
+                        anyhow::bail!(
+                            "no data or heartbeat from {} in over {:?}, treating connection as dead",
+                            self.symbol,
+                            heartbeat_timeout
+                        );
+                    }
                 }
-                _ => {}
             }
         }
 
         Ok(())
     }
 
-    async fn process_message(&self, text: &str) -> Result<()> {
-        // Binance sends wrapped messages
-        #[derive(Deserialize)]
-        struct Wrapper {
-            stream: String,
-            data: serde_json::Value,
-        }
-
-        let wrapper: Wrapper = serde_json::from_str(text)
-            .context("Failed to parse wrapper")?;
-
-        // Parse the inner message based on stream type
-        if wrapper.stream.contains("depth") {
-            let depth_update: BinanceDepthUpdate = serde_json::from_value(wrapper.data)
-                .context("Failed to parse depth update")?;
-            self.handle_depth_update(depth_update).await?;
-        } else if wrapper.stream.contains("aggTrade") {
-            let trade: BinanceAggTrade = serde_json::from_value(wrapper.data)
-                .context("Failed to parse trade")?;
-            self.handle_trade(trade).await?;
+    async fn process_message(&self, text: &str, depth_tx: &mpsc::Sender<DepthUpdate>) -> Result<()> {
+        for message in self.adapter.parse_frame(text)? {
+            match message {
+                ExchangeMessage::Depth(update) => self.handle_depth_update(update, depth_tx).await?,
+                ExchangeMessage::Trade(trade) => self.handle_trade(trade).await?,
+                ExchangeMessage::Kline(kline) => self.handle_kline(kline).await?,
+                ExchangeMessage::BookTicker(ticker) => self.handle_bookticker(ticker).await?,
+            }
         }
 
         Ok(())
     }
 
-    async fn handle_depth_update(&self, update: BinanceDepthUpdate) -> Result<()> {
-        // Convert Binance format to internal format
-        let depth_update = DepthUpdate {
-            symbol: update.symbol.clone(),
-            first_update_id: update.first_update_id,
-            last_update_id: update.last_update_id,
-            bids: update
-                .bids
-                .iter()
-                .map(|(p, q)| PriceLevel {
-                    price: p.parse().unwrap_or(0.0),
-                    quantity: q.parse().unwrap_or(0.0),
-                })
-                .collect(),
-            asks: update
-                .asks
-                .iter()
-                .map(|(p, q)| PriceLevel {
-                    price: p.parse().unwrap_or(0.0),
-                    quantity: q.parse().unwrap_or(0.0),
-                })
-                .collect(),
-        };
-
-        // Update order book
-        match self.orderbook_manager.update(&self.symbol, depth_update.clone()) {
-            Ok(book) => {
-                // Only publish if we have meaningful data
-                if book.bids.len() > 0 && book.asks.len() > 0 {
-                    metrics::ORDERBOOK_UPDATES
-                        .with_label_values(&[&self.symbol])
-                        .inc();
-
-                    // Publish to Redis and shared memory
-                    self.publisher
-                        .publish_orderbook(&book)
-                        .await
-                        .context("Failed to publish orderbook")?;
-
-                    debug!(
-                        "Updated order book for {}: {} bids, {} asks, last_update_id={}",
-                        self.symbol,
-                        book.bids.len(),
-                        book.asks.len(),
-                        book.last_update_id
-                    );
-                }
-            }
-            Err(e) => {
-                // Sequence error - reset orderbook to accept next update as baseline
-                debug!("Sequence error for {}: {}. Resetting to accept next update.", self.symbol, e);
-                metrics::SEQUENCE_ERRORS
-                    .with_label_values(&[&self.symbol])
-                    .inc();
-
-                // Reset orderbook - next update will be accepted as baseline
-                let mut books = self.orderbook_manager.books.write().unwrap();
-                books.remove(&self.symbol);
-            }
-        }
+    async fn handle_depth_update(
+        &self,
+        depth_update: DepthUpdate,
+        depth_tx: &mpsc::Sender<DepthUpdate>,
+    ) -> Result<()> {
+        // Hand off to the depth synchronizer, which buffers against the
+        // REST snapshot and resyncs on any sequence gap.
+        depth_tx
+            .send(depth_update)
+            .await
+            .context("depth synchronizer task has stopped")?;
 
         Ok(())
     }
 
-    async fn handle_trade(&self, trade_data: BinanceAggTrade) -> Result<()> {
-        let trade = Trade {
-            symbol: trade_data.symbol.clone(),
-            trade_id: trade_data.trade_id,
-            price: trade_data.price.parse().unwrap_or(0.0),
-            quantity: trade_data.quantity.parse().unwrap_or(0.0),
-            timestamp: trade_data.timestamp,
-            is_buyer_maker: trade_data.is_buyer_maker,
-        };
-
+    async fn handle_trade(&self, trade: Trade) -> Result<()> {
         metrics::TRADES_PROCESSED
             .with_label_values(&[&self.symbol])
             .inc();
@@ -288,4 +283,35 @@ impl WebSocketClient {
 
         Ok(())
     }
+
+    async fn handle_kline(&self, kline: Kline) -> Result<()> {
+        metrics::KLINES_PROCESSED
+            .with_label_values(&[&self.symbol, &kline.interval])
+            .inc();
+
+        self.publisher
+            .publish_kline(&kline)
+            .await
+            .context("Failed to publish kline")?;
+
+        debug!(
+            "Processed {} kline for {}: close={} closed={}",
+            kline.interval, self.symbol, kline.close, kline.is_closed
+        );
+
+        Ok(())
+    }
+
+    async fn handle_bookticker(&self, ticker: BookTicker) -> Result<()> {
+        metrics::BOOK_TICKER_UPDATES
+            .with_label_values(&[&self.symbol])
+            .inc();
+
+        self.publisher
+            .publish_book_ticker(&ticker)
+            .await
+            .context("Failed to publish book ticker")?;
+
+        Ok(())
+    }
 }
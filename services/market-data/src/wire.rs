@@ -0,0 +1,264 @@
+//! Fixed-width binary codec for the shared-memory hot path.
+//!
+//! `publisher::Publisher` normally serializes to JSON, which is fine for
+//! Redis but too bloated to pack into the mmap ring buffer at trade
+//! frequency. A `TradeRecord` instead serializes to a constant
+//! [`SERIALIZED_SIZE`] so it can be written/read directly out of the ring
+//! with no allocator and no parser on the hot path.
+//!
+//! Byte layout (little-endian throughout):
+//!
+//! ```text
+//! offset  size  field
+//! 0       2     symbol_code    (u16, resolved through a SymbolRegistry)
+//! 2       8     time_ns        (u64, trade timestamp in nanoseconds)
+//! 10      8     price          (f64)
+//! 18      8     quantity       (f64)
+//! 26      1     flags          (bit 0: is_buyer_maker, bit 1: side present, bit 2: side = ask)
+//! 27      4     server_time_ms (u32, coarse publish time; x1_000_000 recovers nanoseconds)
+//! 31      1     reserved
+//! ```
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::orderbook::{PriceLevel, Trade};
+
+pub const SERIALIZED_SIZE: usize = 32;
+
+/// Which side of the book a price level (or the resting order a trade
+/// executed against) belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Bid,
+    Ask,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradeRecord {
+    pub symbol_code: u16,
+    pub time_ns: u64,
+    pub price: f64,
+    pub quantity: f64,
+    pub is_buyer_maker: bool,
+    /// Book side the trade executed against, when known. `is_buyer_maker ==
+    /// true` means the taker sold into the bid, so this is `Bid`.
+    pub side: Option<Side>,
+    pub server_time_ms: u32,
+}
+
+impl TradeRecord {
+    pub fn from_trade(trade: &Trade, symbol_code: u16, server_time_ms: u32) -> Self {
+        let side = Some(if trade.is_buyer_maker { Side::Bid } else { Side::Ask });
+        Self {
+            symbol_code,
+            time_ns: (trade.timestamp.max(0) as u64).saturating_mul(1_000_000),
+            price: trade.price.to_f64().unwrap_or(0.0),
+            quantity: trade.quantity.to_f64().unwrap_or(0.0),
+            is_buyer_maker: trade.is_buyer_maker,
+            side,
+            server_time_ms,
+        }
+    }
+}
+
+/// Encodes `record` into the first [`SERIALIZED_SIZE`] bytes of `buf`.
+pub fn encode_into(buf: &mut [u8], record: &TradeRecord) -> Result<(), String> {
+    if buf.len() < SERIALIZED_SIZE {
+        return Err(format!("buffer too small: {} < {}", buf.len(), SERIALIZED_SIZE));
+    }
+
+    let mut flags = 0u8;
+    if record.is_buyer_maker {
+        flags |= 0b001;
+    }
+    if let Some(side) = record.side {
+        flags |= 0b010;
+        if side == Side::Ask {
+            flags |= 0b100;
+        }
+    }
+
+    buf[0..2].copy_from_slice(&record.symbol_code.to_le_bytes());
+    buf[2..10].copy_from_slice(&record.time_ns.to_le_bytes());
+    buf[10..18].copy_from_slice(&record.price.to_le_bytes());
+    buf[18..26].copy_from_slice(&record.quantity.to_le_bytes());
+    buf[26] = flags;
+    buf[27..31].copy_from_slice(&record.server_time_ms.to_le_bytes());
+    buf[31] = 0; // reserved
+
+    Ok(())
+}
+
+/// Decodes a `TradeRecord` out of the first [`SERIALIZED_SIZE`] bytes of `buf`.
+pub fn decode_from(buf: &[u8]) -> Result<TradeRecord, String> {
+    if buf.len() < SERIALIZED_SIZE {
+        return Err(format!("buffer too small: {} < {}", buf.len(), SERIALIZED_SIZE));
+    }
+
+    let symbol_code = u16::from_le_bytes(buf[0..2].try_into().unwrap());
+    let time_ns = u64::from_le_bytes(buf[2..10].try_into().unwrap());
+    let price = f64::from_le_bytes(buf[10..18].try_into().unwrap());
+    let quantity = f64::from_le_bytes(buf[18..26].try_into().unwrap());
+    let flags = buf[26];
+    let server_time_ms = u32::from_le_bytes(buf[27..31].try_into().unwrap());
+
+    let is_buyer_maker = flags & 0b001 != 0;
+    let side = if flags & 0b010 != 0 {
+        Some(if flags & 0b100 != 0 { Side::Ask } else { Side::Bid })
+    } else {
+        None
+    };
+
+    Ok(TradeRecord {
+        symbol_code,
+        time_ns,
+        price,
+        quantity,
+        is_buyer_maker,
+        side,
+        server_time_ms,
+    })
+}
+
+/// Fixed-width row for one `PriceLevel` of a `DepthUpdate`, tagged with its
+/// side, so a depth update can be encoded as a small header plus a flat
+/// array of rows instead of nested JSON.
+pub const LEVEL_ROW_SIZE: usize = 17;
+
+pub fn encode_level_into(buf: &mut [u8], level: &PriceLevel, side: Side) -> Result<(), String> {
+    if buf.len() < LEVEL_ROW_SIZE {
+        return Err(format!("buffer too small: {} < {}", buf.len(), LEVEL_ROW_SIZE));
+    }
+
+    buf[0..8].copy_from_slice(&level.price.to_f64().unwrap_or(0.0).to_le_bytes());
+    buf[8..16].copy_from_slice(&level.quantity.to_f64().unwrap_or(0.0).to_le_bytes());
+    buf[16] = match side {
+        Side::Bid => 0,
+        Side::Ask => 1,
+    };
+
+    Ok(())
+}
+
+pub fn decode_level_from(buf: &[u8]) -> Result<(PriceLevel, Side), String> {
+    if buf.len() < LEVEL_ROW_SIZE {
+        return Err(format!("buffer too small: {} < {}", buf.len(), LEVEL_ROW_SIZE));
+    }
+
+    let price = f64::from_le_bytes(buf[0..8].try_into().unwrap());
+    let quantity = f64::from_le_bytes(buf[8..16].try_into().unwrap());
+    let side = if buf[16] == 0 { Side::Bid } else { Side::Ask };
+
+    Ok((PriceLevel::new(price, quantity), side))
+}
+
+/// Maps symbols to compact `u16` codes so hot-path records can carry a code
+/// instead of the full string. Codes are assigned on first use and are
+/// stable for the lifetime of the process.
+pub struct SymbolRegistry {
+    inner: RwLock<SymbolRegistryInner>,
+}
+
+struct SymbolRegistryInner {
+    by_symbol: HashMap<String, u16>,
+    by_code: Vec<String>,
+}
+
+impl SymbolRegistry {
+    pub fn new() -> Self {
+        Self {
+            inner: RwLock::new(SymbolRegistryInner {
+                by_symbol: HashMap::new(),
+                by_code: Vec::new(),
+            }),
+        }
+    }
+
+    /// Returns the existing code for `symbol`, registering a new one if this
+    /// is the first time it's been seen.
+    pub fn register(&self, symbol: &str) -> u16 {
+        if let Some(code) = self.inner.read().unwrap().by_symbol.get(symbol) {
+            return *code;
+        }
+
+        let mut inner = self.inner.write().unwrap();
+        if let Some(code) = inner.by_symbol.get(symbol) {
+            return *code;
+        }
+
+        let code = inner.by_code.len() as u16;
+        inner.by_code.push(symbol.to_string());
+        inner.by_symbol.insert(symbol.to_string(), code);
+        code
+    }
+
+    pub fn resolve(&self, code: u16) -> Option<String> {
+        self.inner.read().unwrap().by_code.get(code as usize).cloned()
+    }
+}
+
+impl Default for SymbolRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trade_record_round_trips() {
+        let trade = Trade::new("BTCUSDT", 42, 50123.5, 0.25, 1_700_000_000_123, true);
+        let registry = SymbolRegistry::new();
+        let code = registry.register(&trade.symbol);
+        let record = TradeRecord::from_trade(&trade, code, 123_456);
+
+        let mut buf = [0u8; SERIALIZED_SIZE];
+        encode_into(&mut buf, &record).unwrap();
+        let decoded = decode_from(&buf).unwrap();
+
+        assert_eq!(decoded, record);
+        assert_eq!(decoded.side, Some(Side::Bid));
+        assert_eq!(registry.resolve(code).as_deref(), Some("BTCUSDT"));
+    }
+
+    #[test]
+    fn test_encode_into_rejects_undersized_buffer() {
+        let record = TradeRecord {
+            symbol_code: 0,
+            time_ns: 0,
+            price: 0.0,
+            quantity: 0.0,
+            is_buyer_maker: false,
+            side: None,
+            server_time_ms: 0,
+        };
+        let mut buf = [0u8; SERIALIZED_SIZE - 1];
+        assert!(encode_into(&mut buf, &record).is_err());
+    }
+
+    #[test]
+    fn test_level_row_round_trips() {
+        let level = PriceLevel::new(100.25, 1.5);
+        let mut buf = [0u8; LEVEL_ROW_SIZE];
+        encode_level_into(&mut buf, &level, Side::Ask).unwrap();
+
+        let (decoded, side) = decode_level_from(&buf).unwrap();
+        assert_eq!(decoded.price, level.price);
+        assert_eq!(decoded.quantity, level.quantity);
+        assert_eq!(side, Side::Ask);
+    }
+
+    #[test]
+    fn test_symbol_registry_assigns_stable_codes() {
+        let registry = SymbolRegistry::new();
+        let btc = registry.register("BTCUSDT");
+        let eth = registry.register("ETHUSDT");
+        assert_ne!(btc, eth);
+        assert_eq!(registry.register("BTCUSDT"), btc);
+    }
+}
@@ -0,0 +1,324 @@
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, info, warn};
+
+use crate::orderbook::{OrderBook, OrderBookManager, OrderedFloat, PriceLevel};
+
+type PeerTx = mpsc::UnboundedSender<Message>;
+
+struct Peer {
+    tx: PeerTx,
+    subscriptions: HashSet<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "command", rename_all = "camelCase")]
+enum ClientCommand {
+    Subscribe { market: String },
+    Unsubscribe { market: String },
+    GetMarkets,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum DeltaSide {
+    Bid,
+    Ask,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct LevelDelta {
+    price: f64,
+    quantity: f64,
+    side: DeltaSide,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum DashboardMessage {
+    Checkpoint {
+        market: String,
+        sequence_number: u64,
+        bids: Vec<PriceLevel>,
+        asks: Vec<PriceLevel>,
+    },
+    Delta {
+        market: String,
+        sequence_number: u64,
+        levels: Vec<LevelDelta>,
+    },
+    Markets {
+        markets: Vec<String>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Fans `OrderBook` updates out to subscribed dashboard peers: on
+/// `subscribe`, a peer gets a full checkpoint of the current book; every
+/// update after that is streamed as an incremental level delta tagged with
+/// the book's `last_update_id` as a monotonically increasing sequence
+/// number, so a reconnecting peer can detect a gap and re-subscribe for a
+/// fresh checkpoint.
+pub struct DashboardServer {
+    port: u16,
+    orderbook_manager: Arc<OrderBookManager>,
+    // Connects/disconnects/subscription changes are rare compared to the
+    // per-update broadcast fan-out below, so a `RwLock` lets concurrent
+    // broadcasts read the peer table in parallel instead of serializing
+    // behind a single mutex.
+    peers: RwLock<HashMap<SocketAddr, Peer>>,
+    markets: Mutex<HashSet<String>>,
+}
+
+impl DashboardServer {
+    pub fn new(port: u16, orderbook_manager: Arc<OrderBookManager>) -> Self {
+        Self {
+            port,
+            orderbook_manager,
+            peers: RwLock::new(HashMap::new()),
+            markets: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub async fn run(self: Arc<Self>) -> Result<()> {
+        let addr = format!("0.0.0.0:{}", self.port);
+        let listener = TcpListener::bind(&addr)
+            .await
+            .context("Failed to bind dashboard WebSocket server")?;
+
+        info!("Dashboard WebSocket server listening on {}", addr);
+
+        loop {
+            let (stream, peer_addr) = listener
+                .accept()
+                .await
+                .context("Failed to accept dashboard connection")?;
+
+            let this = Arc::clone(&self);
+            tokio::spawn(async move {
+                if let Err(e) = this.handle_connection(stream, peer_addr).await {
+                    warn!("Dashboard peer {} disconnected: {}", peer_addr, e);
+                }
+            });
+        }
+    }
+
+    async fn handle_connection(
+        &self,
+        stream: tokio::net::TcpStream,
+        addr: SocketAddr,
+    ) -> Result<()> {
+        let ws_stream = tokio_tungstenite::accept_async(stream)
+            .await
+            .context("Dashboard WebSocket handshake failed")?;
+        let (mut write, mut read) = ws_stream.split();
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
+        self.peers.write().await.insert(
+            addr,
+            Peer {
+                tx,
+                subscriptions: HashSet::new(),
+            },
+        );
+        info!("Dashboard peer connected: {}", addr);
+
+        let writer = tokio::spawn(async move {
+            while let Some(msg) = rx.recv().await {
+                if write.send(msg).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        while let Some(msg) = read.next().await {
+            let msg = msg.context("Dashboard WebSocket message error")?;
+            match msg {
+                Message::Text(text) => {
+                    if let Err(e) = self.handle_command(addr, &text).await {
+                        debug!("Error handling command from {}: {}", addr, e);
+                    }
+                }
+                Message::Close(_) => break,
+                _ => {}
+            }
+        }
+
+        self.peers.write().await.remove(&addr);
+        writer.abort();
+        info!("Dashboard peer disconnected: {}", addr);
+
+        Ok(())
+    }
+
+    async fn handle_command(&self, addr: SocketAddr, text: &str) -> Result<()> {
+        let command: ClientCommand =
+            serde_json::from_str(text).context("Failed to parse dashboard command")?;
+
+        match command {
+            ClientCommand::Subscribe { market } => self.subscribe(addr, market).await,
+            ClientCommand::Unsubscribe { market } => self.unsubscribe(addr, &market).await,
+            ClientCommand::GetMarkets => self.send_markets(addr).await,
+        }
+    }
+
+    async fn subscribe(&self, addr: SocketAddr, market: String) -> Result<()> {
+        self.markets.lock().await.insert(market.clone());
+
+        let tx = {
+            let mut peers = self.peers.write().await;
+            let Some(peer) = peers.get_mut(&addr) else {
+                return Ok(());
+            };
+            peer.subscriptions.insert(market.clone());
+            peer.tx.clone()
+        };
+
+        let checkpoint = match self.orderbook_manager.get(&market) {
+            Some(book) => checkpoint_message(&market, &book),
+            None => DashboardMessage::Error {
+                message: format!("unknown market {market}"),
+            },
+        };
+
+        send_to(&tx, &checkpoint)
+    }
+
+    async fn unsubscribe(&self, addr: SocketAddr, market: &str) -> Result<()> {
+        let mut peers = self.peers.write().await;
+        if let Some(peer) = peers.get_mut(&addr) {
+            peer.subscriptions.remove(market);
+        }
+        Ok(())
+    }
+
+    async fn send_markets(&self, addr: SocketAddr) -> Result<()> {
+        let markets: Vec<String> = self.markets.lock().await.iter().cloned().collect();
+
+        let peers = self.peers.read().await;
+        if let Some(peer) = peers.get(&addr) {
+            send_to(&peer.tx, &DashboardMessage::Markets { markets })?;
+        }
+        Ok(())
+    }
+
+    /// Broadcasts a full checkpoint of `book` to every peer subscribed to
+    /// `market`, e.g. after a REST resync rebuilt the book from scratch --
+    /// a delta against the previous (now-stale) book wouldn't be meaningful,
+    /// and a checkpoint lets subscribers converge the same way a fresh
+    /// `subscribe` does.
+    pub async fn broadcast_checkpoint(&self, market: &str, book: &OrderBook) {
+        let message = checkpoint_message(market, book);
+
+        let peers = self.peers.read().await;
+        for peer in peers.values() {
+            if peer.subscriptions.contains(market) {
+                let _ = send_to(&peer.tx, &message);
+            }
+        }
+    }
+
+    /// Broadcast the change from `previous` (the book before this update, if
+    /// any) to `current` as a delta, tagged with `current.last_update_id`,
+    /// to every peer subscribed to `market`.
+    pub async fn broadcast_update(
+        &self,
+        market: &str,
+        previous: Option<&OrderBook>,
+        current: &OrderBook,
+    ) {
+        let levels = diff_levels(previous, current);
+        if levels.is_empty() {
+            return;
+        }
+
+        let message = DashboardMessage::Delta {
+            market: market.to_string(),
+            sequence_number: current.last_update_id,
+            levels,
+        };
+
+        let peers = self.peers.read().await;
+        for peer in peers.values() {
+            if peer.subscriptions.contains(market) {
+                let _ = send_to(&peer.tx, &message);
+            }
+        }
+    }
+}
+
+fn checkpoint_message(market: &str, book: &OrderBook) -> DashboardMessage {
+    let depth = book.bids.len().max(book.asks.len());
+    let (bids, asks) = book.get_top_levels(depth);
+    DashboardMessage::Checkpoint {
+        market: market.to_string(),
+        sequence_number: book.last_update_id,
+        bids,
+        asks,
+    }
+}
+
+fn diff_levels(previous: Option<&OrderBook>, current: &OrderBook) -> Vec<LevelDelta> {
+    let mut deltas = Vec::new();
+
+    diff_side(
+        previous.map(|b| &b.bids),
+        &current.bids,
+        DeltaSide::Bid,
+        &mut deltas,
+    );
+    diff_side(
+        previous.map(|b| &b.asks),
+        &current.asks,
+        DeltaSide::Ask,
+        &mut deltas,
+    );
+
+    deltas
+}
+
+fn diff_side(
+    previous: Option<&std::collections::BTreeMap<OrderedFloat, f64>>,
+    current: &std::collections::BTreeMap<OrderedFloat, f64>,
+    side: DeltaSide,
+    deltas: &mut Vec<LevelDelta>,
+) {
+    for (price, quantity) in current {
+        if previous.and_then(|p| p.get(price)) != Some(quantity) {
+            deltas.push(LevelDelta {
+                price: price.0,
+                quantity: *quantity,
+                side,
+            });
+        }
+    }
+
+    if let Some(previous) = previous {
+        for price in previous.keys() {
+            if !current.contains_key(price) {
+                deltas.push(LevelDelta {
+                    price: price.0,
+                    quantity: 0.0,
+                    side,
+                });
+            }
+        }
+    }
+}
+
+fn send_to(tx: &PeerTx, message: &DashboardMessage) -> Result<()> {
+    let text = serde_json::to_string(message).context("Failed to serialize dashboard message")?;
+    tx.send(Message::Text(text))
+        .context("Dashboard peer channel closed")?;
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
@@ -13,17 +14,45 @@ pub enum CircuitBreakerState {
 /// Circuit breaker configuration.
 #[derive(Debug, Clone)]
 pub struct CircuitBreakerConfig {
+    /// Rolling window over which failures (and the failure ratio) are
+    /// counted. Older entries are evicted on every call rather than reset
+    /// in bulk, so the breaker reacts to the last `window` of traffic
+    /// instead of a raw consecutive-failure streak.
+    pub window: Duration,
+    /// Trips to `Open` once failures within `window` reach this count,
+    /// regardless of how many total requests were made.
     pub max_failures: usize,
+    /// Trips to `Open` once the failure ratio within `window` reaches this
+    /// threshold (0.0-1.0), but only once at least `min_requests` requests
+    /// have been seen -- otherwise a single failed request would trip a
+    /// freshly-reset breaker.
+    pub failure_ratio: f64,
+    pub min_requests: usize,
+    /// Base wait before `Open` probes `HalfOpen`. Actual wait is this value
+    /// doubled for every consecutive trip to `Open`, capped at
+    /// `max_timeout`.
     pub timeout: Duration,
+    pub max_timeout: Duration,
+    /// Requests admitted during one `HalfOpen` period, counting the probe
+    /// that left `Open`. A finished probe does not free its slot.
     pub half_open_max_requests: usize,
+    /// Consecutive `HalfOpen` successes required to close the breaker.
+    /// Independent of `half_open_max_requests`: the latter bounds how many
+    /// probes are admitted, this bounds how many must succeed.
+    pub success_threshold: usize,
 }
 
 impl Default for CircuitBreakerConfig {
     fn default() -> Self {
         CircuitBreakerConfig {
+            window: Duration::from_secs(60),
             max_failures: 5,
+            failure_ratio: 0.5,
+            min_requests: 10,
             timeout: Duration::from_secs(30),
+            max_timeout: Duration::from_secs(5 * 60),
             half_open_max_requests: 3,
+            success_threshold: 2,
         }
     }
 }
@@ -36,10 +65,20 @@ pub struct CircuitBreaker {
 
 struct CircuitBreakerInner {
     state: CircuitBreakerState,
-    failures: usize,
+    /// Timestamps of requests within the rolling window, used as the ratio
+    /// denominator.
+    request_times: VecDeque<Instant>,
+    /// Timestamps of failed requests within the rolling window.
+    failure_times: VecDeque<Instant>,
+    /// Consecutive successes seen so far this `HalfOpen` probation.
     successes: usize,
-    last_fail_time: Option<Instant>,
     half_open_requests: usize,
+    /// When the breaker most recently entered `Open`, used to compute the
+    /// backoff wait before the next `HalfOpen` probe.
+    opened_at: Option<Instant>,
+    /// How many times the breaker has tripped to `Open` in a row without an
+    /// intervening close, driving the exponential backoff.
+    consecutive_open_count: u32,
 }
 
 impl CircuitBreaker {
@@ -49,10 +88,12 @@ impl CircuitBreaker {
             config,
             state: Arc::new(RwLock::new(CircuitBreakerInner {
                 state: CircuitBreakerState::Closed,
-                failures: 0,
+                request_times: VecDeque::new(),
+                failure_times: VecDeque::new(),
                 successes: 0,
-                last_fail_time: None,
                 half_open_requests: 0,
+                opened_at: None,
+                consecutive_open_count: 0,
             })),
         }
     }
@@ -95,26 +136,62 @@ impl CircuitBreaker {
     pub async fn reset(&self) {
         let mut inner = self.state.write().await;
         inner.state = CircuitBreakerState::Closed;
-        inner.failures = 0;
+        inner.request_times.clear();
+        inner.failure_times.clear();
         inner.successes = 0;
         inner.half_open_requests = 0;
+        inner.opened_at = None;
+        inner.consecutive_open_count = 0;
+    }
+
+    fn evict_stale(&self, inner: &mut CircuitBreakerInner, now: Instant) {
+        while let Some(&front) = inner.request_times.front() {
+            if now.duration_since(front) > self.config.window {
+                inner.request_times.pop_front();
+            } else {
+                break;
+            }
+        }
+        while let Some(&front) = inner.failure_times.front() {
+            if now.duration_since(front) > self.config.window {
+                inner.failure_times.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// The Open->HalfOpen wait for the `n`th consecutive trip:
+    /// `timeout * 2^(n-1)`, capped at `max_timeout`.
+    fn backoff(&self, consecutive_open_count: u32) -> Duration {
+        let exponent = consecutive_open_count.saturating_sub(1).min(16);
+        let scaled = self.config.timeout.saturating_mul(1u32 << exponent);
+        scaled.min(self.config.max_timeout)
     }
 
     async fn before_request(&self) -> Result<(), CircuitBreakerError<()>> {
         let mut inner = self.state.write().await;
+        let now = Instant::now();
+        self.evict_stale(&mut inner, now);
 
         match inner.state {
-            CircuitBreakerState::Closed => Ok(()),
+            CircuitBreakerState::Closed => {
+                inner.request_times.push_back(now);
+                Ok(())
+            }
 
             CircuitBreakerState::Open => {
-                if let Some(last_fail) = inner.last_fail_time {
-                    if last_fail.elapsed() > self.config.timeout {
-                        inner.state = CircuitBreakerState::HalfOpen;
-                        inner.half_open_requests = 1;
-                        Ok(())
-                    } else {
-                        Err(CircuitBreakerError::Open)
-                    }
+                let Some(opened_at) = inner.opened_at else {
+                    return Err(CircuitBreakerError::Open);
+                };
+
+                let wait = self.backoff(inner.consecutive_open_count) + jitter(inner.consecutive_open_count);
+                if opened_at.elapsed() > wait {
+                    inner.state = CircuitBreakerState::HalfOpen;
+                    inner.half_open_requests = 1;
+                    inner.successes = 0;
+                    inner.request_times.push_back(now);
+                    Ok(())
                 } else {
                     Err(CircuitBreakerError::Open)
                 }
@@ -125,6 +202,7 @@ impl CircuitBreaker {
                     Err(CircuitBreakerError::TooManyRequests)
                 } else {
                     inner.half_open_requests += 1;
+                    inner.request_times.push_back(now);
                     Ok(())
                 }
             }
@@ -143,16 +221,16 @@ impl CircuitBreaker {
 
     fn on_success(&self, inner: &mut CircuitBreakerInner) {
         match inner.state {
-            CircuitBreakerState::Closed => {
-                inner.failures = 0;
-            }
-
             CircuitBreakerState::HalfOpen => {
                 inner.successes += 1;
-                if inner.successes >= self.config.half_open_max_requests {
+                if inner.successes >= self.config.success_threshold {
                     inner.state = CircuitBreakerState::Closed;
-                    inner.failures = 0;
+                    inner.request_times.clear();
+                    inner.failure_times.clear();
                     inner.successes = 0;
+                    inner.half_open_requests = 0;
+                    inner.opened_at = None;
+                    inner.consecutive_open_count = 0;
                 }
             }
 
@@ -161,18 +239,29 @@ impl CircuitBreaker {
     }
 
     fn on_failure(&self, inner: &mut CircuitBreakerInner) {
-        inner.failures += 1;
-        inner.last_fail_time = Some(Instant::now());
+        let now = Instant::now();
+        inner.failure_times.push_back(now);
 
         match inner.state {
             CircuitBreakerState::Closed => {
-                if inner.failures >= self.config.max_failures {
+                let failures = inner.failure_times.len();
+                let requests = inner.request_times.len();
+                let ratio_tripped = requests >= self.config.min_requests
+                    && failures as f64 / requests as f64 >= self.config.failure_ratio;
+
+                if failures >= self.config.max_failures || ratio_tripped {
                     inner.state = CircuitBreakerState::Open;
+                    inner.opened_at = Some(now);
+                    inner.consecutive_open_count += 1;
                 }
             }
 
             CircuitBreakerState::HalfOpen => {
                 inner.state = CircuitBreakerState::Open;
+                inner.opened_at = Some(now);
+                inner.consecutive_open_count += 1;
+                inner.half_open_requests = 0;
+                inner.successes = 0;
             }
 
             _ => {}
@@ -180,6 +269,23 @@ impl CircuitBreaker {
     }
 }
 
+/// Small bounded jitter (0-49ms, hashed from trip count and clock) so breakers that
+/// tripped at the same instant -- e.g. several endpoints on the same
+/// exchange outage -- don't all probe `HalfOpen` on the same tick.
+fn jitter(consecutive_open_count: u32) -> Duration {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+
+    let mut hasher = DefaultHasher::new();
+    (consecutive_open_count, nanos).hash(&mut hasher);
+    Duration::from_millis(hasher.finish() % 50)
+}
+
 /// Circuit breaker error types.
 #[derive(Debug, thiserror::Error)]
 pub enum CircuitBreakerError<E> {
@@ -192,3 +298,47 @@ pub enum CircuitBreakerError<E> {
     #[error("Inner error: {0}")]
     Inner(E),
 }
+
+/// A keyed collection of `CircuitBreaker`s, one per venue/endpoint, so a
+/// client talking to several exchanges isolates failures on one from the
+/// others instead of sharing a single breaker across all of them.
+pub struct CircuitBreakerRegistry {
+    config: CircuitBreakerConfig,
+    breakers: RwLock<HashMap<String, Arc<CircuitBreaker>>>,
+}
+
+impl CircuitBreakerRegistry {
+    /// Creates a registry where every breaker it creates shares `config`.
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            breakers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the breaker for `key`, creating one with the registry's
+    /// configured defaults if this is the first time `key` has been seen.
+    pub async fn get_or_create(&self, key: &str) -> Arc<CircuitBreaker> {
+        if let Some(breaker) = self.breakers.read().await.get(key) {
+            return Arc::clone(breaker);
+        }
+
+        let mut breakers = self.breakers.write().await;
+        Arc::clone(
+            breakers
+                .entry(key.to_string())
+                .or_insert_with(|| Arc::new(CircuitBreaker::new(self.config.clone()))),
+        )
+    }
+
+    /// Snapshots every known breaker's current state, e.g. for a per-venue
+    /// status panel. Order is unspecified.
+    pub async fn snapshot(&self) -> Vec<(String, CircuitBreakerState)> {
+        let breakers = self.breakers.read().await;
+        let mut states = Vec::with_capacity(breakers.len());
+        for (key, breaker) in breakers.iter() {
+            states.push((key.clone(), breaker.get_state().await));
+        }
+        states
+    }
+}
@@ -1,14 +1,77 @@
 use rust_decimal::Decimal as RustDecimal;
-use serde::{Deserialize, Serialize};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::ops::{Add, Div, Mul, Sub};
 use std::str::FromStr;
 
 /// High-precision decimal type for financial calculations.
 /// Wrapper around rust_decimal to avoid floating point errors.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Decimal(RustDecimal);
 
+impl Serialize for Decimal {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+struct DecimalVisitor;
+
+impl<'de> Visitor<'de> for DecimalVisitor {
+    type Value = Decimal;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a decimal number or a string-encoded decimal, e.g. \"0.00001000\"")
+    }
+
+    // Exchanges frequently send quantity/price fields as JSON strings to
+    // preserve trailing zeros and avoid float precision loss.
+    fn visit_str<E>(self, value: &str) -> Result<Decimal, E>
+    where
+        E: de::Error,
+    {
+        RustDecimal::from_str(value)
+            .map(Decimal)
+            .map_err(de::Error::custom)
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Decimal, E>
+    where
+        E: de::Error,
+    {
+        RustDecimal::from_f64_retain(value)
+            .map(Decimal)
+            .ok_or_else(|| de::Error::custom("f64 value is not representable as a Decimal"))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Decimal, E>
+    where
+        E: de::Error,
+    {
+        Ok(Decimal(RustDecimal::from(value)))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Decimal, E>
+    where
+        E: de::Error,
+    {
+        Ok(Decimal(RustDecimal::from(value)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D>(deserializer: D) -> Result<Decimal, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(DecimalVisitor)
+    }
+}
+
 impl Decimal {
     /// Creates a new Decimal from a string.
     pub fn from_str(s: &str) -> Result<Self, rust_decimal::Error> {
@@ -46,6 +109,11 @@ impl Decimal {
         self.0.to_string()
     }
 
+    /// Returns the string representation rounded to exactly `scale` decimal places.
+    pub fn to_string_with_scale(&self, scale: u32) -> String {
+        format!("{:.*}", scale as usize, self.0)
+    }
+
     /// Returns true if the decimal is zero.
     pub fn is_zero(&self) -> bool {
         self.0.is_zero()
@@ -9,7 +9,10 @@ pub mod errors;
 pub use decimal::Decimal;
 pub use timestamp::Timestamp;
 pub use order_book::{OrderBook, OrderBookLevel, OrderBookSide};
-pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerState};
+pub use circuit_breaker::{
+    CircuitBreaker, CircuitBreakerConfig, CircuitBreakerError, CircuitBreakerRegistry,
+    CircuitBreakerState,
+};
 pub use rate_limiter::RateLimiter;
 pub use id_generator::*;
 pub use errors::*;
@@ -94,6 +94,66 @@ impl RateLimiter {
         let inner = self.inner.lock().await;
         inner.burst as usize
     }
+
+    /// Checks if a request costing `weight` tokens can proceed without
+    /// blocking. Weight-aware alias of `allow_n` for exchange REST endpoints
+    /// that debit more than one unit against a shared request-weight budget
+    /// (e.g. Binance's per-minute weight limit).
+    pub async fn allow_weight(&self, weight: usize) -> bool {
+        self.allow_n(weight).await
+    }
+
+    /// Waits until a request costing `weight` tokens can proceed. Weight-aware
+    /// alias of `wait_n`.
+    pub async fn wait_weight(&self, weight: usize) {
+        self.wait_n(weight).await
+    }
+
+    /// Reserves `weight` tokens immediately, debiting the bucket whether or
+    /// not it currently holds enough, and returns how long the caller must
+    /// sleep before the reservation is honored. Unlike `wait_weight`, the
+    /// lock isn't held across the wait: the deficit is booked up front so a
+    /// scheduler can queue several reservations back-to-back and sleep each
+    /// one out in turn, rather than serializing on the bucket for the whole
+    /// wait.
+    pub async fn reserve(&self, weight: usize) -> Duration {
+        let mut inner = self.inner.lock().await;
+        inner.refill_tokens();
+
+        let weight = weight as f64;
+        if inner.tokens >= weight {
+            inner.tokens -= weight;
+            return Duration::ZERO;
+        }
+
+        let deficit = weight - inner.tokens;
+        inner.tokens -= weight;
+        Duration::from_secs_f64(deficit / inner.rate)
+    }
+
+    /// Reconciles the bucket against a server-reported used-weight value
+    /// (e.g. Binance's `X-MBX-USED-WEIGHT-1M` response header), covering the
+    /// exchange's own `window`. The exchange's reporting window need not
+    /// match this bucket's `rate`/`burst`, so `used` is first normalized onto
+    /// this bucket's own timescale (`burst / rate` seconds) before being
+    /// compared; if the exchange says more weight is gone than we locally
+    /// debited, clamp `tokens` down to match. A lower server count is left
+    /// alone -- it just means some of our earlier requests didn't count
+    /// against the quota we assumed, which is never a reason to hand out
+    /// tokens we didn't already have.
+    pub async fn sync_from_header(&self, used: usize, window: Duration) {
+        let mut inner = self.inner.lock().await;
+        inner.refill_tokens();
+
+        let our_window_secs = inner.burst / inner.rate;
+        let used_per_sec = used as f64 / window.as_secs_f64().max(f64::EPSILON);
+        let equivalent_used = used_per_sec * our_window_secs;
+
+        let server_remaining = (inner.burst - equivalent_used).max(0.0);
+        if server_remaining < inner.tokens {
+            inner.tokens = server_remaining;
+        }
+    }
 }
 
 impl RateLimiterInner {
@@ -137,4 +197,52 @@ mod tests {
         tokio::time::sleep(Duration::from_millis(100)).await;
         assert!(limiter.allow().await);
     }
+
+    #[tokio::test]
+    async fn test_allow_weight_debits_more_than_one_token() {
+        let limiter = RateLimiter::new(10, 10);
+
+        assert!(limiter.allow_weight(6).await);
+        assert!(limiter.allow_weight(4).await);
+        assert!(!limiter.allow_weight(1).await);
+    }
+
+    #[tokio::test]
+    async fn test_reserve_returns_zero_wait_when_tokens_available() {
+        let limiter = RateLimiter::new(10, 10);
+
+        let wait = limiter.reserve(10).await;
+        assert_eq!(wait, Duration::ZERO);
+        // `tokens()` refills for the time since the last call first.
+        assert!(limiter.tokens().await.abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_reserve_books_the_deficit_without_blocking() {
+        let limiter = RateLimiter::new(10, 10);
+
+        let wait = limiter.reserve(15).await;
+        // 5 tokens short at 10/sec -> 0.5s, booked immediately as negative tokens.
+        assert!((wait.as_secs_f64() - 0.5).abs() < 0.01);
+        assert!(limiter.tokens().await < 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_sync_from_header_clamps_down_when_server_reports_more_used() {
+        let limiter = RateLimiter::new(10, 10);
+
+        limiter.sync_from_header(8, Duration::from_secs(1)).await;
+        // `tokens()` refills for the time since the last call first.
+        assert!((limiter.tokens().await - 2.0).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_sync_from_header_never_raises_tokens() {
+        let limiter = RateLimiter::new(10, 10);
+        assert!(limiter.allow_weight(7).await); // 3 tokens left locally
+
+        // Server says only 1 has been used -- we don't hand back the other 2.
+        limiter.sync_from_header(1, Duration::from_secs(1)).await;
+        assert!((limiter.tokens().await - 3.0).abs() < 0.01);
+    }
 }
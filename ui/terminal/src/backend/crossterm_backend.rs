@@ -0,0 +1,81 @@
+//! `backend-crossterm` terminal backend (the default).
+
+use super::{Key, TerminalBackend};
+use crate::keyboard::Action;
+use anyhow::Result;
+use crossterm::{
+    cursor::Show,
+    event::{
+        DisableMouseCapture, EnableMouseCapture, Event, EventStream, KeyCode, KeyModifiers,
+    },
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use futures::StreamExt;
+use ratatui::{backend::CrosstermBackend, Terminal};
+use std::io::Stdout;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::mpsc;
+
+static TORN_DOWN: AtomicBool = AtomicBool::new(false);
+
+pub struct Crossterm;
+
+impl TerminalBackend for Crossterm {
+    type Handle = Terminal<CrosstermBackend<Stdout>>;
+
+    fn init() -> Result<Self::Handle> {
+        enable_raw_mode()?;
+        let mut stdout = std::io::stdout();
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        Ok(Terminal::new(CrosstermBackend::new(stdout))?)
+    }
+
+    fn restore() {
+        if TORN_DOWN.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let mut stdout = std::io::stdout();
+        let _ = execute!(stdout, LeaveAlternateScreen, DisableMouseCapture, Show);
+        let _ = disable_raw_mode();
+    }
+
+    async fn run_events(action_tx: mpsc::Sender<Action>) -> Result<()> {
+        let mut reader = EventStream::new();
+
+        while let Some(event) = reader.next().await {
+            match event {
+                Ok(Event::Key(key)) => {
+                    if let Some(action) = translate(key).and_then(super::map_key) {
+                        action_tx.send(action).await?;
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::error!("Event stream error: {}", e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn translate(key: crossterm::event::KeyEvent) -> Option<Key> {
+    if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        return Some(Key::CtrlC);
+    }
+
+    match key.code {
+        KeyCode::Char(c) => Some(Key::Char(c)),
+        KeyCode::Enter => Some(Key::Enter),
+        KeyCode::Esc => Some(Key::Esc),
+        KeyCode::Backspace => Some(Key::Backspace),
+        KeyCode::Tab => Some(Key::Tab),
+        KeyCode::BackTab => Some(Key::BackTab),
+        KeyCode::Up => Some(Key::Up),
+        KeyCode::Down => Some(Key::Down),
+        _ => None,
+    }
+}
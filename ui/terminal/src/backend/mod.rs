@@ -0,0 +1,94 @@
+//! Pluggable terminal backend, selected at compile time by a cargo feature:
+//! `backend-crossterm` (default) or `backend-termion`. Terminal setup and
+//! teardown, event polling, and the key-to-`Action` mapping all go through
+//! this module, so `main`, `crate::terminal`, and the render loop never
+//! reference a concrete backend crate directly -- adding a third backend
+//! later only means adding another module here and wiring it into the
+//! `cfg`s below.
+//!
+//! `ratatui::Frame` is already backend-agnostic (the backend type lives on
+//! `ratatui::Terminal<B>`, not on `Frame`), so panel `render` functions
+//! need no changes to work under either backend.
+
+#[cfg(all(feature = "backend-crossterm", feature = "backend-termion"))]
+compile_error!(
+    "exactly one of the `backend-crossterm`/`backend-termion` features must be enabled, not both"
+);
+#[cfg(not(any(feature = "backend-crossterm", feature = "backend-termion")))]
+compile_error!(
+    "exactly one of the `backend-crossterm`/`backend-termion` features must be enabled"
+);
+
+use crate::keyboard::Action;
+use anyhow::Result;
+use tokio::sync::mpsc;
+
+#[cfg(feature = "backend-crossterm")]
+mod crossterm_backend;
+#[cfg(feature = "backend-crossterm")]
+pub use crossterm_backend::Crossterm as Active;
+
+#[cfg(feature = "backend-termion")]
+mod termion_backend;
+#[cfg(feature = "backend-termion")]
+pub use termion_backend::Termion as Active;
+
+/// The `ratatui::Terminal<...>` type produced by the compiled-in backend.
+pub type Handle = <Active as TerminalBackend>::Handle;
+
+/// Backend-agnostic key press, translated from the platform's native event
+/// type at the edge (each backend's `translate`), so `map_key` below -- and
+/// everything upstream of it -- never matches on crossterm or termion types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    CtrlC,
+    Enter,
+    Esc,
+    Backspace,
+    Tab,
+    BackTab,
+    Up,
+    Down,
+}
+
+/// Terminal lifecycle and event polling for one backend. Exactly one impl
+/// is ever compiled in, selected by the `backend-*` features above.
+pub trait TerminalBackend {
+    type Handle;
+
+    /// Enables raw mode, enters the alternate screen, and constructs the
+    /// `ratatui::Terminal`.
+    fn init() -> Result<Self::Handle>;
+
+    /// Restores the terminal. Idempotent, and safe to call from both the
+    /// normal shutdown path and the panic hook (see `crate::terminal`).
+    fn restore();
+
+    /// Runs the event-polling loop until the input stream ends, sending
+    /// mapped `Action`s to `action_tx`.
+    fn run_events(action_tx: mpsc::Sender<Action>) -> impl std::future::Future<Output = Result<()>> + Send;
+}
+
+/// Maps a single backend-agnostic key press to an `Action`. Shared by every
+/// backend's event loop so the keybindings themselves live in exactly one
+/// place instead of being duplicated per backend.
+pub fn map_key(key: Key) -> Option<Action> {
+    match key {
+        Key::CtrlC => Some(Action::Quit),
+        Key::Char('q') => Some(Action::Quit),
+        Key::Char('?') => Some(Action::ShowHelp),
+        Key::Char('r') => Some(Action::ReloadConfig),
+        Key::Tab => Some(Action::NextPanel),
+        Key::BackTab => Some(Action::PrevPanel),
+        Key::Char(':') => Some(Action::EnterCommandMode),
+        Key::Char('c') => Some(Action::CancelSelectedOrder),
+        Key::Char('C') => Some(Action::CancelAllOrders),
+        Key::Char('x') => Some(Action::CloseSelectedPosition),
+        Key::Char('X') => Some(Action::CloseAllPositions),
+        Key::Char('l') => Some(Action::CycleLogLevel),
+        Key::Up | Key::Char('k') => Some(Action::ScrollUp),
+        Key::Down | Key::Char('j') => Some(Action::ScrollDown),
+        _ => None,
+    }
+}
@@ -0,0 +1,81 @@
+//! `backend-termion` terminal backend, for environments where crossterm's
+//! raw-mode/alternate-screen handling behaves worse than termion's.
+
+use super::{Key, TerminalBackend};
+use crate::keyboard::Action;
+use anyhow::Result;
+use ratatui::{backend::TermionBackend, Terminal};
+use std::io::{Stdout, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use termion::event::Key as RawKey;
+use termion::input::TermRead;
+use termion::raw::{IntoRawMode, RawTerminal};
+use termion::screen::{AlternateScreen, IntoAlternateScreen};
+use tokio::sync::mpsc;
+
+static TORN_DOWN: AtomicBool = AtomicBool::new(false);
+
+type Screen = AlternateScreen<RawTerminal<Stdout>>;
+
+pub struct Termion;
+
+impl TerminalBackend for Termion {
+    type Handle = Terminal<TermionBackend<Screen>>;
+
+    fn init() -> Result<Self::Handle> {
+        let screen = std::io::stdout().into_raw_mode()?.into_alternate_screen()?;
+        Ok(Terminal::new(TermionBackend::new(screen))?)
+    }
+
+    fn restore() {
+        if TORN_DOWN.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        // Dropping the `Terminal`'s `AlternateScreen`/`RawTerminal` on
+        // normal shutdown restores the screen and cooked mode. This only
+        // re-shows the cursor; it does not leave the alternate screen or
+        // raw mode itself.
+        let mut stdout = std::io::stdout();
+        let _ = write!(stdout, "{}", termion::cursor::Show);
+        let _ = stdout.flush();
+    }
+
+    async fn run_events(action_tx: mpsc::Sender<Action>) -> Result<()> {
+        // termion's `Keys` iterator blocks on stdin reads, so it runs on a
+        // dedicated OS thread and forwards translated keys back over a
+        // tokio channel instead of an async stream like crossterm's.
+        let (raw_tx, mut raw_rx) = mpsc::channel::<RawKey>(100);
+        std::thread::spawn(move || {
+            for key in std::io::stdin().keys().flatten() {
+                if raw_tx.blocking_send(key).is_err() {
+                    break;
+                }
+            }
+        });
+
+        while let Some(key) = raw_rx.recv().await {
+            if let Some(action) = translate(key).and_then(super::map_key) {
+                action_tx.send(action).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn translate(key: RawKey) -> Option<Key> {
+    match key {
+        RawKey::Ctrl('c') => Some(Key::CtrlC),
+        RawKey::Char('\n') => Some(Key::Enter),
+        RawKey::Char('\t') => Some(Key::Tab),
+        RawKey::Char(c) => Some(Key::Char(c)),
+        RawKey::Esc => Some(Key::Esc),
+        RawKey::Backspace => Some(Key::Backspace),
+        RawKey::Up => Some(Key::Up),
+        RawKey::Down => Some(Key::Down),
+        // termion's basic `Keys` iterator doesn't distinguish Shift+Tab
+        // from other escape sequences, so back-tab has no mapping here.
+        _ => None,
+    }
+}
@@ -0,0 +1,194 @@
+//! Throttles how often bursty `StateUpdate`s reach `AppState::apply_update`.
+//!
+//! Under heavy order-book traffic, applying every single `StateUpdate`
+//! immediately sets `dirty.all` far more often than a human can perceive,
+//! burning CPU on renders nobody sees. `UpdateCoalescer` sits between the
+//! update channel and `AppState`: it keeps only the latest `OrderBook`,
+//! `ConnectionStatus`, `Positions` and `Orders`, concatenates pending
+//! `Candles`/`Symbols` lists, and is flushed into `apply_update` at most once per
+//! configured tick (`ui.max_render_fps`) rather than once per message.
+//!
+//! `Fills`/`Signals`/`Alerts` don't pass through here at all -- they're
+//! high-frequency enough to warrant their own lock-free path (see `ring.rs`)
+//! rather than being batched and then funneled back through the same
+//! `AppState` lock this coalescer also writes through.
+
+use crate::state::StateUpdate;
+use crate::types::{Candle, ConnectionStatus, Order, Position, ServerCapabilities, Symbol};
+use crate::types::OrderBook;
+
+#[derive(Default)]
+pub struct UpdateCoalescer {
+    orderbook: Option<OrderBook>,
+    connection_status: Option<(ConnectionStatus, u64)>,
+    positions: Option<Vec<Position>>,
+    orders: Option<Vec<Order>>,
+    candles: Vec<Candle>,
+    symbols: Vec<Symbol>,
+    capabilities: Option<ServerCapabilities>,
+    dirty: bool,
+}
+
+impl UpdateCoalescer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Folds `update` into the pending batch. Returns `true` for
+    /// high-priority updates (currently just `ConnectionStatus`, since a
+    /// connection transition should be visible immediately rather than
+    /// waiting out the coalescing interval) so the caller can flush right
+    /// away instead of waiting for the next tick.
+    pub fn push(&mut self, update: StateUpdate) -> bool {
+        self.dirty = true;
+
+        match update {
+            StateUpdate::OrderBook(book) => {
+                self.orderbook = Some(book);
+                false
+            }
+            StateUpdate::ConnectionStatus(status, latency_ms) => {
+                self.connection_status = Some((status, latency_ms));
+                true
+            }
+            StateUpdate::Positions(positions) => {
+                self.positions = Some(positions);
+                false
+            }
+            StateUpdate::Orders(orders) => {
+                self.orders = Some(orders);
+                false
+            }
+            StateUpdate::Candles(mut candles) => {
+                self.candles.append(&mut candles);
+                false
+            }
+            StateUpdate::Symbols(mut symbols) => {
+                self.symbols.append(&mut symbols);
+                false
+            }
+            StateUpdate::Capabilities(capabilities) => {
+                self.capabilities = Some(capabilities);
+                false
+            }
+            StateUpdate::Fills(_) | StateUpdate::Signals(_) | StateUpdate::Alerts(_) => {
+                // These are routed around the coalescer entirely; callers
+                // shouldn't push them here, but folding them in as a no-op
+                // is safer than panicking on a future call-site mistake.
+                false
+            }
+        }
+    }
+
+    /// Applies the pending batch via `apply` (normally wrapping
+    /// `AppState::apply_update`) and clears it. No-op if nothing has been
+    /// folded in since the last flush.
+    pub fn flush(&mut self, apply: &mut impl FnMut(StateUpdate)) {
+        if !self.dirty {
+            return;
+        }
+
+        if let Some(book) = self.orderbook.take() {
+            apply(StateUpdate::OrderBook(book));
+        }
+        if let Some((status, latency_ms)) = self.connection_status.take() {
+            apply(StateUpdate::ConnectionStatus(status, latency_ms));
+        }
+        if let Some(positions) = self.positions.take() {
+            apply(StateUpdate::Positions(positions));
+        }
+        if let Some(orders) = self.orders.take() {
+            apply(StateUpdate::Orders(orders));
+        }
+        if !self.candles.is_empty() {
+            apply(StateUpdate::Candles(std::mem::take(&mut self.candles)));
+        }
+        if !self.symbols.is_empty() {
+            apply(StateUpdate::Symbols(std::mem::take(&mut self.symbols)));
+        }
+        if let Some(capabilities) = self.capabilities.take() {
+            apply(StateUpdate::Capabilities(capabilities));
+        }
+
+        self.dirty = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConnectionStatus;
+
+    fn sample_orderbook(symbol: &str) -> OrderBook {
+        OrderBook {
+            symbol: symbol.to_string(),
+            bids: vec![],
+            asks: vec![],
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    fn sample_candle(symbol: &str) -> Candle {
+        Candle {
+            symbol: symbol.to_string(),
+            resolution: "1m".to_string(),
+            open_time: chrono::Utc::now(),
+            open: common::Decimal::zero(),
+            high: common::Decimal::zero(),
+            low: common::Decimal::zero(),
+            close: common::Decimal::zero(),
+            base_volume: common::Decimal::zero(),
+            quote_volume: common::Decimal::zero(),
+        }
+    }
+
+    #[test]
+    fn test_orderbook_bursts_collapse_to_the_latest() {
+        let mut coalescer = UpdateCoalescer::new();
+        coalescer.push(StateUpdate::OrderBook(sample_orderbook("BTCUSDT")));
+        coalescer.push(StateUpdate::OrderBook(sample_orderbook("ETHUSDT")));
+
+        let mut applied = Vec::new();
+        coalescer.flush(&mut |u| applied.push(u));
+
+        assert_eq!(applied.len(), 1);
+        match &applied[0] {
+            StateUpdate::OrderBook(book) => assert_eq!(book.symbol, "ETHUSDT"),
+            _ => panic!("expected OrderBook"),
+        }
+    }
+
+    #[test]
+    fn test_candles_accumulate_across_pushes() {
+        let mut coalescer = UpdateCoalescer::new();
+        coalescer.push(StateUpdate::Candles(vec![sample_candle("BTCUSDT")]));
+        coalescer.push(StateUpdate::Candles(vec![sample_candle("ETHUSDT")]));
+
+        let mut applied = Vec::new();
+        coalescer.flush(&mut |u| applied.push(u));
+
+        assert_eq!(applied.len(), 1);
+        match &applied[0] {
+            StateUpdate::Candles(candles) => assert_eq!(candles.len(), 2),
+            _ => panic!("expected Candles"),
+        }
+    }
+
+    #[test]
+    fn test_connection_status_reports_high_priority() {
+        let mut coalescer = UpdateCoalescer::new();
+        let high_priority = coalescer.push(StateUpdate::ConnectionStatus(ConnectionStatus::Connected, 5));
+        assert!(high_priority);
+
+        let not_high_priority = coalescer.push(StateUpdate::Positions(vec![]));
+        assert!(!not_high_priority);
+    }
+
+    #[test]
+    fn test_flush_without_pending_updates_is_a_noop() {
+        let mut coalescer = UpdateCoalescer::new();
+        let mut applied = Vec::new();
+        coalescer.flush(&mut |u| applied.push(u));
+        assert!(applied.is_empty());
+    }
+}
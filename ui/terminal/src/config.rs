@@ -10,6 +10,7 @@ pub struct Config {
     pub keyboard: KeyboardConfig,
     pub performance: PerformanceConfig,
     pub logging: LoggingConfig,
+    pub nats: NatsConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,7 +26,29 @@ pub struct UiConfig {
     pub refresh_rate_ms: u64,
     pub color_scheme: String,
     pub show_milliseconds: bool,
-    pub stale_data_threshold_s: u64,
+    /// How long since `AppState::last_update` before the status bar's
+    /// `⚠ STALE DATA` indicator fires. In milliseconds rather than whole
+    /// seconds, so deployments that expect sub-second updates can tune it
+    /// tightly.
+    pub stale_threshold_ms: u64,
+    /// Status bar latency text turns `Theme::warning` at or above this
+    /// round-trip latency.
+    pub latency_warn_threshold_ms: u64,
+    /// Status bar latency text turns `Theme::error` at or above this
+    /// round-trip latency.
+    pub latency_critical_threshold_ms: u64,
+    /// Maximum cadence, in frames per second, at which coalesced state
+    /// updates are committed to `AppState` (see `UpdateCoalescer`). Caps how
+    /// often a bursty feed like the order book can force a re-render,
+    /// independent of `refresh_rate_ms`, which paces the terminal redraw
+    /// itself.
+    pub max_render_fps: u32,
+    /// Whether glyph-heavy panels (AI Signals' strength bar, the status
+    /// bar's connection dot and stale-data warning) may use Unicode
+    /// block/box-drawing characters. Disable on terminals that render them
+    /// as tofu or misaligned (tmux over SSH, Windows conhost, minimal
+    /// fonts) to fall back to plain-ASCII equivalents.
+    pub enhanced_graphics: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,9 +56,12 @@ pub struct PanelsConfig {
     pub default_symbol: String,
     pub max_fills_display: usize,
     pub max_signals_display: usize,
+    pub max_candles_display: usize,
     pub max_alerts_display: usize,
     pub orderbook_depth_levels: usize,
     pub alert_auto_dismiss_s: u64,
+    /// Capacity of the in-app Logs panel's ring buffer of `tracing` records.
+    pub max_logs_display: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,6 +87,16 @@ pub struct LoggingConfig {
     pub json: bool,
 }
 
+/// Optional fan-out sink that mirrors incoming dashboard state updates onto
+/// NATS subjects, so headless deployments can distribute market data to
+/// other services without speaking the dashboard's own websocket protocol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NatsConfig {
+    pub enabled: bool,
+    pub url: String,
+    pub subject_prefix: String,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -74,15 +110,21 @@ impl Default for Config {
                 refresh_rate_ms: 100,
                 color_scheme: "default".to_string(),
                 show_milliseconds: false,
-                stale_data_threshold_s: 5,
+                stale_threshold_ms: 5000,
+                latency_warn_threshold_ms: 50,
+                latency_critical_threshold_ms: 150,
+                max_render_fps: 30,
+                enhanced_graphics: true,
             },
             panels: PanelsConfig {
                 default_symbol: "BTCUSDT".to_string(),
                 max_fills_display: 50,
                 max_signals_display: 20,
+                max_candles_display: 200,
                 max_alerts_display: 100,
                 orderbook_depth_levels: 10,
                 alert_auto_dismiss_s: 30,
+                max_logs_display: 1000,
             },
             keyboard: KeyboardConfig {
                 quit_keys: vec!["q".to_string()],
@@ -101,6 +143,11 @@ impl Default for Config {
                 file: String::new(),
                 json: false,
             },
+            nats: NatsConfig {
+                enabled: false,
+                url: "nats://127.0.0.1:4222".to_string(),
+                subject_prefix: "md".to_string(),
+            },
         }
     }
 }
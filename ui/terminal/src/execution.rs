@@ -0,0 +1,560 @@
+use crate::keyboard::Action;
+use crate::state::AppState;
+use crate::types::{self, Alert, AlertLevel, Order, OrderSide, OrderStatus, OrderType};
+use crate::websocket::{ClientMessage, PendingOrders};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use common::{CircuitBreakerConfig, CircuitBreakerError, CircuitBreakerRegistry, Decimal};
+use parking_lot::RwLock;
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot, Mutex as AsyncMutex};
+use tokio::task::JoinHandle;
+use tokio::time::{timeout, Duration};
+
+/// Outcome of routing an order through the execution client.
+#[derive(Debug, Clone)]
+pub enum OrderAck {
+    Accepted { order_id: String },
+    Rejected { reason: String },
+}
+
+/// Submits an order and blocks until the venue acks or rejects it, retrying
+/// automatically if the dashboard connection drops mid-flight.
+#[async_trait]
+pub trait SyncClient {
+    async fn submit_order(&self, order: &Order) -> Result<OrderAck>;
+    async fn cancel_order(&self, order_id: &str) -> Result<OrderAck>;
+}
+
+/// Fire-and-forget submission: returns the client-order id immediately and
+/// leaves the ack/reject to arrive later over the normal state-update path.
+#[async_trait]
+pub trait AsyncClient {
+    async fn submit_order_async(&self, order: &Order) -> Result<String>;
+    async fn cancel_order_async(&self, order_id: &str) -> Result<String>;
+}
+
+/// Routes orders out over the same dashboard websocket connection used for
+/// inbound state updates, matching acks/rejects back to callers via a
+/// shared table of `client_order_id -> oneshot::Sender`.
+pub struct WsExecutionClient {
+    outbound_tx: mpsc::Sender<ClientMessage>,
+    pending: PendingOrders,
+    ack_timeout: Duration,
+    max_retries: u32,
+}
+
+impl WsExecutionClient {
+    pub fn new(outbound_tx: mpsc::Sender<ClientMessage>, pending: PendingOrders) -> Self {
+        Self {
+            outbound_tx,
+            pending,
+            ack_timeout: Duration::from_secs(5),
+            max_retries: 3,
+        }
+    }
+
+    fn submit_message(client_order_id: &str, order: &Order) -> ClientMessage {
+        ClientMessage::SubmitOrder {
+            client_order_id: client_order_id.to_string(),
+            symbol: order.symbol.clone(),
+            side: order.side.clone(),
+            order_type: order.order_type.clone(),
+            price: order.price,
+            size: order.size,
+        }
+    }
+
+    async fn send_and_await(&self, client_order_id: String, msg: ClientMessage) -> Result<OrderAck> {
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(client_order_id.clone(), tx);
+
+        if self.outbound_tx.send(msg).await.is_err() {
+            self.pending.lock().await.remove(&client_order_id);
+            return Err(anyhow!("dashboard connection is closed"));
+        }
+
+        match timeout(self.ack_timeout, rx).await {
+            Ok(Ok(ack)) => Ok(ack),
+            Ok(Err(_)) => Err(anyhow!("dashboard connection dropped before order was acked")),
+            Err(_) => {
+                self.pending.lock().await.remove(&client_order_id);
+                Err(anyhow!("timed out waiting for order ack"))
+            }
+        }
+    }
+
+    async fn send_with_retry(&self, client_order_id: String, msg: ClientMessage) -> Result<OrderAck> {
+        let mut attempt = 0;
+        loop {
+            match self.send_and_await(client_order_id.clone(), msg.clone()).await {
+                Ok(ack) => return Ok(ack),
+                Err(e) if attempt < self.max_retries => {
+                    attempt += 1;
+                    tracing::warn!(
+                        "order {} submission attempt {} failed ({}), retrying",
+                        client_order_id,
+                        attempt,
+                        e
+                    );
+                    tokio::time::sleep(Duration::from_millis(250 * attempt as u64)).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SyncClient for WsExecutionClient {
+    async fn submit_order(&self, order: &Order) -> Result<OrderAck> {
+        let client_order_id = common::generate_client_order_id("terminal");
+        let msg = Self::submit_message(&client_order_id, order);
+        self.send_with_retry(client_order_id, msg).await
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<OrderAck> {
+        let client_order_id = common::generate_client_order_id("terminal");
+        let msg = ClientMessage::CancelOrder {
+            client_order_id: client_order_id.clone(),
+            order_id: order_id.to_string(),
+        };
+        self.send_with_retry(client_order_id, msg).await
+    }
+}
+
+#[async_trait]
+impl AsyncClient for WsExecutionClient {
+    async fn submit_order_async(&self, order: &Order) -> Result<String> {
+        let client_order_id = common::generate_client_order_id("terminal");
+        let msg = Self::submit_message(&client_order_id, order);
+        self.outbound_tx
+            .send(msg)
+            .await
+            .map_err(|_| anyhow!("dashboard connection is closed"))?;
+        Ok(client_order_id)
+    }
+
+    async fn cancel_order_async(&self, order_id: &str) -> Result<String> {
+        let client_order_id = common::generate_client_order_id("terminal");
+        let msg = ClientMessage::CancelOrder {
+            client_order_id: client_order_id.clone(),
+            order_id: order_id.to_string(),
+        };
+        self.outbound_tx
+            .send(msg)
+            .await
+            .map_err(|_| anyhow!("dashboard connection is closed"))?;
+        Ok(client_order_id)
+    }
+}
+
+fn push_error_alert(state: &Arc<RwLock<AppState>>, message: String) {
+    let mut state = state.write();
+    state.set_action_feedback(AlertLevel::Error, message.clone());
+    state.apply_update(crate::state::StateUpdate::Alerts(vec![Alert {
+        id: common::generate_request_id(),
+        level: AlertLevel::Error,
+        message,
+        timestamp: chrono::Utc::now(),
+    }]));
+}
+
+/// Confirms a successful order action in the command-mode status line.
+/// Unlike `push_error_alert`, this doesn't also raise an alert -- a
+/// confirmed submit/cancel/close is the expected outcome, not something
+/// that warrants a persistent entry in the alerts panel.
+fn push_success_feedback(state: &Arc<RwLock<AppState>>, message: String) {
+    state.write().set_action_feedback(AlertLevel::Info, message);
+}
+
+/// Submits `order` via the sync execution client and reflects the result in
+/// state: an ack moves the order into state.orders, a reject raises an alert.
+pub async fn submit_and_record(
+    order: Order,
+    state: &Arc<RwLock<AppState>>,
+    exec_client: &Arc<WsExecutionClient>,
+) {
+    match exec_client.submit_order(&order).await {
+        Ok(OrderAck::Accepted { order_id }) => {
+            let mut accepted = order;
+            accepted.id = order_id.clone();
+            accepted.status = OrderStatus::New;
+            state.write().upsert_order(accepted);
+            push_success_feedback(state, format!("Order {order_id} accepted"));
+        }
+        Ok(OrderAck::Rejected { reason }) => {
+            push_error_alert(state, format!("Order rejected: {reason}"));
+        }
+        Err(e) => {
+            push_error_alert(state, format!("Order submission failed: {e}"));
+        }
+    }
+}
+
+pub async fn cancel_order(order_id: &str, state: &Arc<RwLock<AppState>>, exec_client: &Arc<WsExecutionClient>) {
+    match exec_client.cancel_order(order_id).await {
+        Ok(OrderAck::Accepted { .. }) => {
+            tracing::info!("Order {} canceled", order_id);
+            push_success_feedback(state, format!("Order {order_id} canceled"));
+        }
+        Ok(OrderAck::Rejected { reason }) => {
+            push_error_alert(state, format!("Cancel rejected: {reason}"));
+        }
+        Err(e) => {
+            push_error_alert(state, format!("Cancel request failed: {e}"));
+        }
+    }
+}
+
+pub async fn close_position(symbol: &str, state: &Arc<RwLock<AppState>>, exec_client: &Arc<WsExecutionClient>) {
+    let position = state
+        .read()
+        .positions
+        .iter()
+        .find(|p| p.symbol == symbol)
+        .cloned();
+
+    let Some(position) = position else {
+        push_error_alert(state, format!("No open position for {symbol}"));
+        return;
+    };
+
+    let side = match position.side {
+        types::PositionSide::Long => OrderSide::Sell,
+        types::PositionSide::Short => OrderSide::Buy,
+    };
+
+    let order = Order {
+        id: String::new(),
+        symbol: symbol.to_string(),
+        side,
+        order_type: OrderType::Market,
+        price: Decimal::zero(),
+        size: position.size.abs(),
+        filled_size: Decimal::zero(),
+        status: OrderStatus::New,
+        timestamp: chrono::Utc::now(),
+        linked_order_id: None,
+    };
+
+    submit_and_record(order, state, exec_client).await;
+}
+
+pub async fn execute_command(
+    cmd: &str,
+    state: &Arc<RwLock<AppState>>,
+    exec_client: &Arc<WsExecutionClient>,
+) -> Result<()> {
+    let parts: Vec<&str> = cmd.split_whitespace().collect();
+    if parts.is_empty() {
+        return Ok(());
+    }
+
+    match parts[0] {
+        // :buy BTCUSDT 0.01 @ 65000 [limit|market]
+        "buy" | "sell" => {
+            if parts.len() < 5 || parts[3] != "@" {
+                tracing::warn!("Invalid order command format. Usage: buy/sell <symbol> <size> @ <price> [order_type]");
+                return Ok(());
+            }
+
+            let side = if parts[0] == "buy" { OrderSide::Buy } else { OrderSide::Sell };
+            let symbol = parts[1].to_string();
+            let (Ok(size), Ok(price)) = (parts[2].parse::<f64>(), parts[4].parse::<f64>()) else {
+                tracing::warn!("Invalid size/price in command: {}", cmd);
+                return Ok(());
+            };
+            let order_type = match parts.get(5).copied().unwrap_or("limit") {
+                "market" => OrderType::Market,
+                _ => OrderType::Limit,
+            };
+
+            if let Some(reason) = validate_against_symbol(state, &symbol, price, size) {
+                push_error_alert(state, reason);
+                return Ok(());
+            }
+
+            let order = Order {
+                id: String::new(),
+                symbol,
+                side,
+                order_type,
+                price: Decimal::from_f64(price).unwrap_or(Decimal::zero()),
+                size: Decimal::from_f64(size).unwrap_or(Decimal::zero()),
+                filled_size: Decimal::zero(),
+                status: OrderStatus::New,
+                timestamp: chrono::Utc::now(),
+                linked_order_id: None,
+            };
+
+            submit_and_record(order, state, exec_client).await;
+        }
+        // market <buy/sell> <symbol> <size>
+        "market" => {
+            if parts.len() < 4 {
+                tracing::warn!("Invalid market order format. Usage: market <buy/sell> <symbol> <size>");
+                return Ok(());
+            }
+            let side = if parts[1] == "buy" { OrderSide::Buy } else { OrderSide::Sell };
+            let symbol = parts[2].to_string();
+            let Ok(size) = parts[3].parse::<f64>() else {
+                tracing::warn!("Invalid size in command: {}", cmd);
+                return Ok(());
+            };
+
+            let order = Order {
+                id: String::new(),
+                symbol,
+                side,
+                order_type: OrderType::Market,
+                price: Decimal::zero(),
+                size: Decimal::from_f64(size).unwrap_or(Decimal::zero()),
+                filled_size: Decimal::zero(),
+                status: OrderStatus::New,
+                timestamp: chrono::Utc::now(),
+                linked_order_id: None,
+            };
+
+            submit_and_record(order, state, exec_client).await;
+        }
+        "cancel" => {
+            if parts.len() < 2 {
+                tracing::warn!("Invalid cancel command. Usage: cancel <order_id>");
+                return Ok(());
+            }
+            cancel_order(parts[1], state, exec_client).await;
+        }
+        "close" => {
+            if parts.len() < 2 {
+                tracing::warn!("Invalid close command. Usage: close <symbol>");
+                return Ok(());
+            }
+            close_position(parts[1], state, exec_client).await;
+        }
+        "theme" => {
+            if parts.len() < 2 {
+                tracing::warn!("Invalid theme command. Usage: theme <name>");
+                return Ok(());
+            }
+            state.write().set_theme(parts[1]);
+        }
+        _ => {
+            tracing::warn!("Unknown command: {}", parts[0]);
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates a price/size pair against the symbol's known exchange filters,
+/// if any have been received yet. Returns `None` when the order is valid or
+/// no filters are known for the symbol.
+fn validate_against_symbol(
+    state: &Arc<RwLock<AppState>>,
+    symbol: &str,
+    price: f64,
+    size: f64,
+) -> Option<String> {
+    let reject = state.read().symbols.get(symbol)?.validate_order(price, size).err()?;
+    Some(format!("{symbol} order rejected by exchange filters: {reject:?}"))
+}
+
+/// Cancels every currently-open order, guarded by a circuit breaker keyed on
+/// this action kind: once cancels have been failing, further cancels in the
+/// same batch fail fast with an alert instead of hammering a degraded venue.
+async fn cancel_all_orders(
+    state: &Arc<RwLock<AppState>>,
+    exec_client: &Arc<WsExecutionClient>,
+    breakers: &CircuitBreakerRegistry,
+) {
+    let order_ids: Vec<String> = state
+        .read()
+        .orders
+        .iter()
+        .filter(|o| matches!(o.status, OrderStatus::New | OrderStatus::PartiallyFilled))
+        .map(|o| o.id.clone())
+        .collect();
+
+    if order_ids.is_empty() {
+        return;
+    }
+
+    let breaker = breakers.get_or_create("cancel_all_orders").await;
+    for order_id in order_ids {
+        let client = exec_client.clone();
+        let order_id_for_call = order_id.clone();
+        let result = breaker
+            .execute_async(|| async move { client.cancel_order(&order_id_for_call).await })
+            .await;
+
+        match result {
+            Ok(OrderAck::Accepted { .. }) => {
+                tracing::info!("Order {} canceled", order_id);
+            }
+            Ok(OrderAck::Rejected { reason }) => {
+                push_error_alert(state, format!("Cancel rejected for {order_id}: {reason}"));
+            }
+            Err(CircuitBreakerError::Open) | Err(CircuitBreakerError::TooManyRequests) => {
+                push_error_alert(
+                    state,
+                    "cancel-all-orders circuit breaker is open, stopping".to_string(),
+                );
+                break;
+            }
+            Err(CircuitBreakerError::Inner(e)) => {
+                push_error_alert(state, format!("Cancel failed for {order_id}: {e}"));
+            }
+        }
+    }
+}
+
+/// Closes every open position with a market order, guarded the same way as
+/// `cancel_all_orders`.
+async fn close_all_positions(
+    state: &Arc<RwLock<AppState>>,
+    exec_client: &Arc<WsExecutionClient>,
+    breakers: &CircuitBreakerRegistry,
+) {
+    let positions = state.read().positions.clone();
+    if positions.is_empty() {
+        return;
+    }
+
+    let breaker = breakers.get_or_create("close_all_positions").await;
+    for position in positions {
+        let side = match position.side {
+            types::PositionSide::Long => OrderSide::Sell,
+            types::PositionSide::Short => OrderSide::Buy,
+        };
+        let order = Order {
+            id: String::new(),
+            symbol: position.symbol.clone(),
+            side,
+            order_type: OrderType::Market,
+            price: Decimal::zero(),
+            size: position.size.abs(),
+            filled_size: Decimal::zero(),
+            status: OrderStatus::New,
+            timestamp: chrono::Utc::now(),
+            linked_order_id: None,
+        };
+
+        let client = exec_client.clone();
+        let order_for_call = order.clone();
+        let result = breaker
+            .execute_async(|| async move { client.submit_order(&order_for_call).await })
+            .await;
+
+        match result {
+            Ok(OrderAck::Accepted { order_id }) => {
+                let mut accepted = order;
+                accepted.id = order_id;
+                accepted.status = OrderStatus::New;
+                state.write().upsert_order(accepted);
+            }
+            Ok(OrderAck::Rejected { reason }) => {
+                push_error_alert(state, format!("Close rejected for {}: {reason}", position.symbol));
+            }
+            Err(CircuitBreakerError::Open) | Err(CircuitBreakerError::TooManyRequests) => {
+                push_error_alert(
+                    state,
+                    "close-all-positions circuit breaker is open, stopping".to_string(),
+                );
+                break;
+            }
+            Err(CircuitBreakerError::Inner(e)) => {
+                push_error_alert(state, format!("Close failed for {}: {e}", position.symbol));
+            }
+        }
+    }
+}
+
+/// Runs side-effecting `Action`s (cancel-all, close-all, free-form commands)
+/// on a small pool of workers pulling from a shared queue, so a slow
+/// exchange round-trip never blocks the keyboard/render loop that sends
+/// them. Cancel-all and close-all each get their own entry in a
+/// `CircuitBreakerRegistry`, so a degraded cancel endpoint doesn't also
+/// throttle close-all; free-form commands go through no breaker.
+pub struct ActionWorkerPool {
+    queue_tx: mpsc::Sender<Action>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl ActionWorkerPool {
+    pub fn spawn(
+        worker_count: usize,
+        queue_capacity: usize,
+        exec_client: Arc<WsExecutionClient>,
+        state: Arc<RwLock<AppState>>,
+    ) -> Self {
+        let (queue_tx, queue_rx) = mpsc::channel(queue_capacity);
+        let queue_rx = Arc::new(AsyncMutex::new(queue_rx));
+        let breakers = Arc::new(CircuitBreakerRegistry::new(CircuitBreakerConfig::default()));
+
+        let workers = (0..worker_count)
+            .map(|id| {
+                tokio::spawn(worker_loop(
+                    id,
+                    queue_rx.clone(),
+                    exec_client.clone(),
+                    state.clone(),
+                    breakers.clone(),
+                ))
+            })
+            .collect();
+
+        Self { queue_tx, workers }
+    }
+
+    /// Queues `action` for a worker to pick up. Errors only once every
+    /// worker has exited (e.g. after `shutdown`).
+    pub async fn submit(&self, action: Action) -> Result<()> {
+        self.queue_tx
+            .send(action)
+            .await
+            .map_err(|_| anyhow!("action worker pool is shut down"))
+    }
+
+    /// Stops accepting new work and waits for whatever each worker already
+    /// pulled off the queue to finish, rather than aborting it mid-flight.
+    pub async fn shutdown(self) {
+        drop(self.queue_tx);
+        for worker in self.workers {
+            if let Err(e) = worker.await {
+                tracing::warn!("action worker task panicked: {}", e);
+            }
+        }
+    }
+}
+
+async fn worker_loop(
+    id: usize,
+    queue_rx: Arc<AsyncMutex<mpsc::Receiver<Action>>>,
+    exec_client: Arc<WsExecutionClient>,
+    state: Arc<RwLock<AppState>>,
+    breakers: Arc<CircuitBreakerRegistry>,
+) {
+    loop {
+        let action = {
+            let mut rx = queue_rx.lock().await;
+            rx.recv().await
+        };
+
+        let Some(action) = action else { break };
+
+        match action {
+            Action::Quit => break,
+            Action::CancelAllOrders => cancel_all_orders(&state, &exec_client, &breakers).await,
+            Action::CloseAllPositions => close_all_positions(&state, &exec_client, &breakers).await,
+            Action::ExecuteCommand(cmd) => {
+                if let Err(e) = execute_command(&cmd, &state, &exec_client).await {
+                    tracing::error!("command execution error: {}", e);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    tracing::debug!("action worker {} shutting down", id);
+}
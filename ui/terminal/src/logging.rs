@@ -0,0 +1,139 @@
+//! In-memory sink for `tracing` events, feeding the in-app Logs panel.
+//!
+//! Registered as an extra `tracing_subscriber::Layer` alongside the existing
+//! fmt layer (see `main::init_logging`), so every `tracing::info!`/`warn!`/
+//! etc. call already scattered through the app lands here too without call
+//! sites changing. `LogLayer` is the writer, called from whatever thread
+//! logged; `LogBuffer` is the bounded, shared ring buffer it writes into;
+//! the Logs panel is the reader, snapshotting it once per render.
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    fn from_tracing(level: &Level) -> Self {
+        match *level {
+            Level::ERROR => LogLevel::Error,
+            Level::WARN => LogLevel::Warn,
+            Level::INFO => LogLevel::Info,
+            Level::DEBUG | Level::TRACE => LogLevel::Debug,
+        }
+    }
+
+    /// Cycles the minimum-level filter, wrapping from `Error` back to `Debug`.
+    pub fn cycle(self) -> Self {
+        match self {
+            LogLevel::Debug => LogLevel::Info,
+            LogLevel::Info => LogLevel::Warn,
+            LogLevel::Warn => LogLevel::Error,
+            LogLevel::Error => LogLevel::Debug,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    pub timestamp: DateTime<Utc>,
+    pub level: LogLevel,
+    pub target: String,
+    pub message: String,
+}
+
+/// Shared, bounded ring buffer of `LogRecord`s, oldest evicted first once
+/// `capacity` is exceeded.
+#[derive(Clone)]
+pub struct LogBuffer {
+    records: Arc<Mutex<VecDeque<LogRecord>>>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: Arc::new(Mutex::new(VecDeque::new())),
+            capacity: capacity.max(1),
+        }
+    }
+
+    fn push(&self, record: LogRecord) {
+        let mut records = self.records.lock();
+        records.push_back(record);
+        while records.len() > self.capacity {
+            records.pop_front();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.lock().is_empty()
+    }
+
+    /// Snapshot of everything currently buffered, oldest first.
+    pub fn snapshot(&self) -> Vec<LogRecord> {
+        self.records.lock().iter().cloned().collect()
+    }
+}
+
+/// `tracing_subscriber::Layer` that mirrors every event into a `LogBuffer`.
+pub struct LogLayer {
+    buffer: LogBuffer,
+}
+
+impl LogLayer {
+    pub fn new(buffer: LogBuffer) -> Self {
+        Self { buffer }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        self.buffer.push(LogRecord {
+            timestamp: Utc::now(),
+            level: LogLevel::from_tracing(event.metadata().level()),
+            target: event.metadata().target().to_string(),
+            message: visitor.message,
+        });
+    }
+}
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        }
+    }
+}
@@ -1,26 +1,35 @@
 use anyhow::Result;
 use clap::Parser;
-use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
-use ratatui::{backend::CrosstermBackend, Terminal};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use parking_lot::RwLock;
 
+use coalescer::UpdateCoalescer;
+
+mod backend;
+mod coalescer;
 mod config;
+mod execution;
+mod logging;
+mod nats_publisher;
+mod ring;
 mod state;
+mod terminal;
 mod ui;
 mod websocket;
 mod keyboard;
 mod types;
 
+use backend::TerminalBackend;
 use config::Config;
+use execution::{ActionWorkerPool, WsExecutionClient};
+use nats_publisher::NatsPublisher;
 use state::{AppState, StateUpdate};
+use std::collections::HashMap;
+use types::Alert;
 use websocket::WsClient;
-use keyboard::{KeyboardHandler, Action};
+use keyboard::Action;
 
 #[derive(Parser, Debug)]
 #[command(name = "b25-terminal-ui")]
@@ -59,13 +68,16 @@ async fn main() -> Result<()> {
         config.logging.level = level;
     }
 
-    // Initialize logging
-    init_logging(&config)?;
+    // Initialize logging. `log_buffer` is created here, before the
+    // subscriber exists, so it can be handed to both the `logging::LogLayer`
+    // registered below and the Logs panel's `AppState` once `run_app` starts.
+    let log_buffer = logging::LogBuffer::new(config.panels.max_logs_display);
+    init_logging(&config, log_buffer.clone())?;
     tracing::info!("Starting B25 Terminal UI");
     tracing::debug!("Configuration: {:?}", config);
 
     // Run the application
-    if let Err(e) = run_app(config).await {
+    if let Err(e) = run_app(config, log_buffer).await {
         tracing::error!("Application error: {}", e);
         eprintln!("Error: {}", e);
         return Err(e);
@@ -74,38 +86,130 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn run_app(config: Config) -> Result<()> {
-    // Setup terminal
-    enable_raw_mode()?;
-    let mut stdout = std::io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+async fn run_app(config: Config, log_buffer: logging::LogBuffer) -> Result<()> {
+    // Setup terminal via the compiled-in `backend::Active`. The panic hook
+    // and `_terminal_guard` both restore it exactly once (see
+    // `backend::TerminalBackend::restore`), covering the panic path and
+    // every early return out of this function respectively.
+    terminal::install_panic_hook();
+    let mut terminal = backend::Active::init()?;
+    let _terminal_guard = terminal::TerminalGuard::new();
 
     // Initialize shared state
-    let state = Arc::new(RwLock::new(AppState::new(config.clone())));
+    let state = Arc::new(RwLock::new(AppState::new(config.clone(), log_buffer)));
+
+    // Fills/signals/alerts arrive in ingestion bursts; route them through a
+    // lock-free SPSC ring each instead of the `state` RwLock so the ingest
+    // side never waits on the render loop. The render loop drains these into
+    // `state`'s display cache once per tick instead of once per message.
+    let (fill_producer, mut fill_consumer) = ring::spsc::<types::Fill>(config.panels.max_fills_display);
+    let (signal_producer, mut signal_consumer) = ring::spsc::<types::Signal>(config.panels.max_signals_display);
+    let (alert_producer, mut alert_consumer) = ring::spsc::<Alert>(config.panels.max_alerts_display);
 
     // Create channels
     let (state_tx, mut state_rx) = mpsc::channel::<StateUpdate>(1000);
     let (action_tx, mut action_rx) = mpsc::channel::<Action>(100);
+    let (outbound_tx, outbound_rx) = mpsc::channel::<websocket::ClientMessage>(100);
+    let pending: websocket::PendingOrders = Arc::new(tokio::sync::Mutex::new(HashMap::new()));
 
     // Spawn WebSocket client
-    let ws_client = WsClient::new(config.connection.clone(), state_tx);
+    let ws_client = WsClient::new(
+        config.connection.clone(),
+        state_tx,
+        outbound_rx,
+        pending.clone(),
+    );
     let ws_handle = tokio::spawn(async move {
         ws_client.connect_with_retry().await
     });
 
-    // Spawn keyboard handler
-    let keyboard_handler = KeyboardHandler::new(action_tx.clone());
-    let keyboard_handle = tokio::spawn(async move {
-        keyboard_handler.run().await
-    });
+    // Order execution client, routed over the same dashboard socket above
+    let exec_client = Arc::new(WsExecutionClient::new(outbound_tx, pending));
+
+    // Side-effecting actions (cancel-all, close-all, free-form commands) run
+    // on a small worker pool instead of inline in the action loop below, so a
+    // slow exchange round-trip can't freeze keyboard input. Cancel-all and
+    // close-all each get their own circuit breaker via the pool's registry.
+    let action_pool = ActionWorkerPool::spawn(4, 100, exec_client.clone(), state.clone());
+
+    // Spawn the compiled-in backend's event loop
+    let keyboard_handle = tokio::spawn(backend::Active::run_events(action_tx.clone()));
+
+    // Optional fan-out sink: mirrors every incoming state update onto NATS
+    // subjects so headless deployments can distribute market data without
+    // speaking this dashboard's websocket protocol.
+    let nats_publisher = if config.nats.enabled {
+        match NatsPublisher::connect(&config.nats.url, config.nats.subject_prefix.clone()).await {
+            Ok(publisher) => Some(publisher),
+            Err(e) => {
+                tracing::error!("Failed to connect to NATS at {}: {}", config.nats.url, e);
+                None
+            }
+        }
+    } else {
+        None
+    };
 
-    // Spawn state updater
+    // Spawn state updater. Fills/signals/alerts go straight to their lock-free
+    // rings; everything else is folded into a `UpdateCoalescer` and only
+    // committed to `state` at `ui.max_render_fps`, so a burst of order-book
+    // ticks doesn't force a re-render on every single message. Connection
+    // status changes bypass the timer and flush immediately.
     let state_clone = state.clone();
+    let max_render_fps = config.ui.max_render_fps.max(1);
     tokio::spawn(async move {
-        while let Some(update) = state_rx.recv().await {
-            state_clone.write().apply_update(update);
+        let mut coalescer = UpdateCoalescer::new();
+        let mut coalesce_tick = tokio::time::interval(Duration::from_secs_f64(1.0 / max_render_fps as f64));
+
+        // Routes one update to its ring (Fills/Signals/Alerts) or folds it
+        // into `coalescer`, flushing immediately if it's high-priority.
+        let mut route = |update: StateUpdate, coalescer: &mut UpdateCoalescer| {
+            if let Some(publisher) = &nats_publisher {
+                publisher.publish_update(&update);
+            }
+
+            match update {
+                StateUpdate::Fills(fills) => {
+                    for fill in fills {
+                        fill_producer.push(fill);
+                    }
+                }
+                StateUpdate::Signals(signals) => {
+                    for signal in signals {
+                        signal_producer.push(signal);
+                    }
+                }
+                StateUpdate::Alerts(alerts) => {
+                    for alert in alerts {
+                        alert_producer.push(alert);
+                    }
+                }
+                other => {
+                    if coalescer.push(other) {
+                        coalescer.flush(&mut |u| state_clone.write().apply_update(u));
+                    }
+                }
+            }
+        };
+
+        loop {
+            tokio::select! {
+                _ = coalesce_tick.tick() => {
+                    coalescer.flush(&mut |u| state_clone.write().apply_update(u));
+                }
+                maybe_update = state_rx.recv() => {
+                    let Some(update) = maybe_update else { break };
+                    route(update, &mut coalescer);
+
+                    // Drain whatever else is already queued without
+                    // blocking, so a burst that arrived between ticks gets
+                    // folded into one batch rather than trickling through
+                    // `tokio::select!` one message at a time.
+                    while let Ok(update) = state_rx.try_recv() {
+                        route(update, &mut coalescer);
+                    }
+                }
+            }
         }
     });
 
@@ -117,6 +221,16 @@ async fn run_app(config: Config) -> Result<()> {
     let result = loop {
         tokio::select! {
             _ = interval.tick() => {
+                // Drain the lock-free ingest rings into the display cache
+                // once per tick, rather than taking the write lock on every
+                // individual fill/signal/alert as it arrives.
+                {
+                    let mut state = state.write();
+                    state.drain_fills(&mut fill_consumer);
+                    state.drain_signals(&mut signal_consumer);
+                    state.drain_alerts(&mut alert_consumer);
+                }
+
                 // Render UI
                 if let Err(e) = terminal.draw(|f| {
                     ui::render(f, &state);
@@ -136,7 +250,7 @@ async fn run_app(config: Config) -> Result<()> {
                         break Ok(());
                     }
                     action => {
-                        if let Err(e) = handle_action(action, &state).await {
+                        if let Err(e) = handle_action(action, &state, &exec_client, &action_pool).await {
                             tracing::error!("Action handler error: {}", e);
                         }
                     }
@@ -145,23 +259,24 @@ async fn run_app(config: Config) -> Result<()> {
         }
     };
 
-    // Cleanup
+    // Cleanup. The action pool gets a graceful shutdown rather than an abort
+    // so a cancel-all/close-all already in flight finishes instead of being
+    // cut off mid-request.
     tracing::info!("Shutting down...");
+    action_pool.shutdown().await;
     ws_handle.abort();
     keyboard_handle.abort();
 
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
-
+    // Terminal restoration happens when `_terminal_guard` drops below.
     result
 }
 
-async fn handle_action(action: Action, state: &Arc<RwLock<AppState>>) -> Result<()> {
+async fn handle_action(
+    action: Action,
+    state: &Arc<RwLock<AppState>>,
+    exec_client: &Arc<WsExecutionClient>,
+    action_pool: &ActionWorkerPool,
+) -> Result<()> {
     match action {
         Action::NextPanel => {
             state.write().next_panel();
@@ -189,19 +304,33 @@ async fn handle_action(action: Action, state: &Arc<RwLock<AppState>>) -> Result<
             state.write().command_backspace();
         }
         Action::ExecuteCommand(cmd) => {
-            tracing::info!("Executing command: {}", cmd);
-            execute_command(&cmd, state).await?;
+            tracing::info!("Queuing command: {}", cmd);
+            if action_pool.submit(Action::ExecuteCommand(cmd)).await.is_err() {
+                tracing::error!("action worker pool is shut down, dropping command");
+            }
         }
         Action::CancelSelectedOrder => {
             if let Some(order_id) = state.read().get_selected_order_id() {
                 tracing::info!("Canceling selected order: {}", order_id);
-                // TODO: Send cancel request to order execution service
+                execution::cancel_order(&order_id, state, exec_client).await;
+            }
+        }
+        Action::CancelAllOrders => {
+            tracing::info!("Queuing cancel-all-orders");
+            if action_pool.submit(Action::CancelAllOrders).await.is_err() {
+                tracing::error!("action worker pool is shut down, dropping cancel-all");
             }
         }
         Action::CloseSelectedPosition => {
             if let Some(symbol) = state.read().get_selected_position_symbol() {
                 tracing::info!("Closing selected position: {}", symbol);
-                // TODO: Send close position request
+                execution::close_position(&symbol, state, exec_client).await;
+            }
+        }
+        Action::CloseAllPositions => {
+            tracing::info!("Queuing close-all-positions");
+            if action_pool.submit(Action::CloseAllPositions).await.is_err() {
+                tracing::error!("action worker pool is shut down, dropping close-all");
             }
         }
         Action::ScrollUp => {
@@ -210,80 +339,23 @@ async fn handle_action(action: Action, state: &Arc<RwLock<AppState>>) -> Result<
         Action::ScrollDown => {
             state.write().scroll_down();
         }
-        _ => {}
-    }
-    Ok(())
-}
-
-async fn execute_command(cmd: &str, state: &Arc<RwLock<AppState>>) -> Result<()> {
-    let parts: Vec<&str> = cmd.split_whitespace().collect();
-    if parts.is_empty() {
-        return Ok(());
-    }
-
-    match parts[0] {
-        "buy" | "sell" => {
-            if parts.len() < 4 {
-                tracing::warn!("Invalid order command format. Usage: buy/sell <symbol> <size> <price>");
-                return Ok(());
-            }
-            let side = parts[0];
-            let symbol = parts[1];
-            let size = parts[2].parse::<f64>().ok();
-            let price = parts[3].parse::<f64>().ok();
-
-            if let (Some(size), Some(price)) = (size, price) {
-                tracing::info!("Placing {} order: {} {} @ {}", side, symbol, size, price);
-                // TODO: Send order request to order execution service
-            }
-        }
-        "market" => {
-            if parts.len() < 3 {
-                tracing::warn!("Invalid market order format. Usage: market <buy/sell> <symbol> <size>");
-                return Ok(());
-            }
-            let side = parts[1];
-            let symbol = parts[2];
-            let size = parts[3].parse::<f64>().ok();
-
-            if let Some(size) = size {
-                tracing::info!("Placing market {} order: {} {}", side, symbol, size);
-                // TODO: Send market order request
-            }
-        }
-        "cancel" => {
-            if parts.len() < 2 {
-                tracing::warn!("Invalid cancel command. Usage: cancel <order_id>");
-                return Ok(());
-            }
-            let order_id = parts[1];
-            tracing::info!("Canceling order: {}", order_id);
-            // TODO: Send cancel request
-        }
-        "close" => {
-            if parts.len() < 2 {
-                tracing::warn!("Invalid close command. Usage: close <symbol>");
-                return Ok(());
-            }
-            let symbol = parts[1];
-            tracing::info!("Closing position: {}", symbol);
-            // TODO: Send close position request
-        }
-        _ => {
-            tracing::warn!("Unknown command: {}", parts[0]);
+        Action::CycleLogLevel => {
+            state.write().cycle_log_min_level();
         }
+        Action::Quit => {}
     }
-
     Ok(())
 }
 
-fn init_logging(config: &Config) -> Result<()> {
+fn init_logging(config: &Config, log_buffer: logging::LogBuffer) -> Result<()> {
     use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new(&config.logging.level));
 
-    let registry = tracing_subscriber::registry().with(env_filter);
+    let registry = tracing_subscriber::registry()
+        .with(env_filter)
+        .with(logging::LogLayer::new(log_buffer));
 
     if config.logging.json {
         registry
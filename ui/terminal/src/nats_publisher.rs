@@ -0,0 +1,84 @@
+use crate::state::StateUpdate;
+use anyhow::{Context, Result};
+use async_nats::Client;
+
+/// Mirrors incoming `StateUpdate`s onto hierarchical NATS subjects (e.g.
+/// `md.orderbook.BTCUSDT`, `md.fills.BTCUSDT`) so headless deployments can
+/// distribute market data to other services without speaking the
+/// dashboard's own websocket protocol. Publishing is fire-and-forget: a
+/// failure is logged and dropped rather than propagated, since a downstream
+/// NATS outage must never stall the `state_tx` channel driving the UI.
+pub struct NatsPublisher {
+    client: Client,
+    subject_prefix: String,
+}
+
+impl NatsPublisher {
+    pub async fn connect(url: &str, subject_prefix: String) -> Result<Self> {
+        let client = async_nats::connect(url)
+            .await
+            .context("Failed to connect to NATS")?;
+        Ok(Self { client, subject_prefix })
+    }
+
+    /// Publishes `update` to the subject(s) its message type/symbol derive
+    /// to, if any. Updates with no natural per-symbol subject (orders,
+    /// symbols, connection status, capabilities) are not published.
+    pub fn publish_update(&self, update: &StateUpdate) {
+        match update {
+            StateUpdate::OrderBook(book) => self.publish_one("orderbook", &book.symbol, book),
+            StateUpdate::Positions(positions) => {
+                for position in positions {
+                    self.publish_one("positions", &position.symbol, position);
+                }
+            }
+            StateUpdate::Fills(fills) => {
+                for fill in fills {
+                    self.publish_one("fills", &fill.symbol, fill);
+                }
+            }
+            StateUpdate::Signals(signals) => {
+                for signal in signals {
+                    self.publish_one("signals", &signal.symbol, signal);
+                }
+            }
+            StateUpdate::Candles(candles) => {
+                for candle in candles {
+                    self.publish_one("candles", &candle.symbol, candle);
+                }
+            }
+            StateUpdate::Alerts(alerts) => {
+                let subject = format!("{}.alerts", self.subject_prefix);
+                for alert in alerts {
+                    self.publish(&subject, alert);
+                }
+            }
+            StateUpdate::Orders(_)
+            | StateUpdate::Symbols(_)
+            | StateUpdate::ConnectionStatus(..)
+            | StateUpdate::Capabilities(_) => {}
+        }
+    }
+
+    fn publish_one<T: serde::Serialize>(&self, kind: &str, symbol: &str, payload: &T) {
+        let subject = format!("{}.{}.{}", self.subject_prefix, kind, symbol);
+        self.publish(&subject, payload);
+    }
+
+    fn publish<T: serde::Serialize>(&self, subject: &str, payload: &T) {
+        let body = match serde_json::to_vec(payload) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!("Failed to serialize payload for {}: {}", subject, e);
+                return;
+            }
+        };
+
+        let client = self.client.clone();
+        tokio::spawn(async move {
+            if let Err(e) = client.publish(subject.clone(), body.into()).await {
+                tracing::warn!("Failed to publish to NATS subject {}: {}", subject, e);
+            }
+        });
+    }
+}
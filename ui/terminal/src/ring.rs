@@ -0,0 +1,267 @@
+//! Bounded single-producer/single-consumer lock-free ring buffer.
+//!
+//! `AppState` previously kept `fills`/`signals`/`alerts` as plain `VecDeque`s
+//! mutated under the same `RwLock` the render loop reads every tick, so a
+//! bursty ingestion task contends with rendering on every push. `SpscRing`
+//! gives those high-frequency feeds a lock-free path: the producer writes
+//! without ever touching the consumer's lock, and the consumer drains into
+//! the display cache on its own schedule.
+//!
+//! Only one `Producer` and one `Consumer` are ever created per ring (via
+//! `SpscRing::new`), so `tail` has exactly one writer (the producer) and
+//! needs nothing beyond a plain Acquire/Release pair. `head` is different:
+//! the consumer advances it on every `pop`, but the producer *also* needs
+//! to advance it to evict the oldest entry when the ring is full, so both
+//! sides can race to claim the same slot. `head` is therefore advanced with
+//! `compare_exchange` on both sides -- whichever side's CAS wins owns that
+//! slot's read/drop, and the other retries -- rather than a plain store.
+
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+struct Slot<T>(UnsafeCell<MaybeUninit<T>>);
+
+struct SpscRing<T> {
+    buffer: Box<[Slot<T>]>,
+    capacity: usize,
+    /// Next slot the consumer will read from. Advanced with
+    /// `compare_exchange` by the consumer on `pop` and by the producer when
+    /// it evicts from a full ring; the producer also reads it to compute
+    /// free space.
+    head: AtomicUsize,
+    /// Next slot the producer will write to. Written only by the producer
+    /// (Release), read by the consumer (Acquire) to compute availability.
+    tail: AtomicUsize,
+}
+
+// SAFETY: slots are only ever accessed by whichever side (producer or
+// consumer) currently owns that index, established by the head/tail
+// Acquire/Release protocol below.
+unsafe impl<T: Send> Send for SpscRing<T> {}
+unsafe impl<T: Send> Sync for SpscRing<T> {}
+
+impl<T> SpscRing<T> {
+    /// Creates a ring of the given capacity, split into its producer and
+    /// consumer halves. Panics if `capacity` is zero.
+    fn new(capacity: usize) -> (Producer<T>, Consumer<T>) {
+        assert!(capacity > 0, "SpscRing capacity must be non-zero");
+
+        let buffer = (0..capacity)
+            .map(|_| Slot(UnsafeCell::new(MaybeUninit::uninit())))
+            .collect::<Vec<_>>()
+            .into_boxed_slice();
+
+        let inner = Arc::new(SpscRing {
+            buffer,
+            capacity,
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        });
+
+        (
+            Producer { inner: Arc::clone(&inner) },
+            Consumer { inner },
+        )
+    }
+}
+
+impl<T> Drop for SpscRing<T> {
+    fn drop(&mut self) {
+        // Drop whatever's still buffered between head and tail; both
+        // Producer and Consumer are gone by now, so plain loads are fine.
+        let mut head = *self.head.get_mut();
+        let tail = *self.tail.get_mut();
+        while head != tail {
+            let index = head % self.capacity;
+            unsafe { std::ptr::drop_in_place(self.buffer[index].0.get() as *mut T) };
+            head = head.wrapping_add(1);
+        }
+    }
+}
+
+/// The write half of an `SpscRing`. Create a pair with `spsc(capacity)`.
+pub struct Producer<T> {
+    inner: Arc<SpscRing<T>>,
+}
+
+/// The read half of an `SpscRing`. Create a pair with `spsc(capacity)`.
+pub struct Consumer<T> {
+    inner: Arc<SpscRing<T>>,
+}
+
+/// Creates a bounded SPSC ring of `capacity` slots, returning its producer
+/// and consumer halves.
+pub fn spsc<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    SpscRing::new(capacity)
+}
+
+impl<T> Producer<T> {
+    /// Pushes `value`. If the ring is full, overwrites the oldest unread
+    /// entry (dropping it) rather than blocking -- the "keep newest N"
+    /// semantics the bounded feeds need, without a separate trim pass.
+    pub fn push(&self, value: T) {
+        let ring = &*self.inner;
+        let tail = ring.tail.load(Ordering::Relaxed);
+        let mut head = ring.head.load(Ordering::Acquire);
+
+        if tail.wrapping_sub(head) >= ring.capacity {
+            // Evict the oldest entry to make room. The consumer may be
+            // popping concurrently and could claim this same slot first,
+            // so advancing `head` and reading/dropping the slot it pointed
+            // to must happen atomically together -- whichever side's CAS
+            // succeeds is the one that actually owns (and must drop) that
+            // slot; the loser backs off and rechecks.
+            loop {
+                let stale = head % ring.capacity;
+                match ring.head.compare_exchange(
+                    head,
+                    head.wrapping_add(1),
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => {
+                        unsafe { std::ptr::drop_in_place(ring.buffer[stale].0.get() as *mut T) };
+                        break;
+                    }
+                    Err(new_head) => {
+                        head = new_head;
+                        // The consumer may have already drained enough for
+                        // this push to fit without evicting anything.
+                        if tail.wrapping_sub(head) < ring.capacity {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        let index = tail % ring.capacity;
+        unsafe { (*ring.buffer[index].0.get()).write(value) };
+        ring.tail.store(tail.wrapping_add(1), Ordering::Release);
+    }
+}
+
+impl<T> Consumer<T> {
+    /// Pops the oldest unread entry, if any.
+    pub fn pop(&mut self) -> Option<T> {
+        let ring = &*self.inner;
+        loop {
+            let head = ring.head.load(Ordering::Acquire);
+            let tail = ring.tail.load(Ordering::Acquire);
+
+            if tail.wrapping_sub(head) == 0 {
+                return None;
+            }
+
+            // Claim the slot before reading it: the producer may be
+            // evicting this same oldest entry concurrently (see
+            // `Producer::push`), so whichever side wins the CAS is the one
+            // that actually reads/drops `buffer[index]`.
+            let index = head % ring.capacity;
+            if ring
+                .head
+                .compare_exchange(head, head.wrapping_add(1), Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                let value = unsafe { (*ring.buffer[index].0.get()).assume_init_read() };
+                return Some(value);
+            }
+        }
+    }
+
+    /// Drains every currently-available entry, oldest first, into `sink`.
+    pub fn drain_into(&mut self, sink: &mut impl FnMut(T)) {
+        while let Some(value) = self.pop() {
+            sink(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_then_pop_round_trips_in_fifo_order() {
+        let (producer, mut consumer) = spsc::<u32>(4);
+
+        producer.push(1);
+        producer.push(2);
+        producer.push(3);
+
+        assert_eq!(consumer.pop(), Some(1));
+        assert_eq!(consumer.pop(), Some(2));
+        assert_eq!(consumer.pop(), Some(3));
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn test_push_past_capacity_overwrites_oldest() {
+        let (producer, mut consumer) = spsc::<u32>(3);
+
+        for i in 0..5 {
+            producer.push(i);
+        }
+
+        // Only the newest 3 of [0,1,2,3,4] should survive.
+        assert_eq!(consumer.pop(), Some(2));
+        assert_eq!(consumer.pop(), Some(3));
+        assert_eq!(consumer.pop(), Some(4));
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn test_drain_into_collects_everything_available() {
+        let (producer, mut consumer) = spsc::<u32>(8);
+        for i in 0..5 {
+            producer.push(i);
+        }
+
+        let mut collected = Vec::new();
+        consumer.drain_into(&mut |v| collected.push(v));
+
+        assert_eq!(collected, vec![0, 1, 2, 3, 4]);
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn test_dropping_ring_with_unread_entries_drops_each_value() {
+        use std::sync::atomic::AtomicUsize as Counter;
+        use std::sync::Arc as Shared;
+
+        struct DropCounter(Shared<Counter>);
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let drops = Shared::new(Counter::new(0));
+        {
+            let (producer, consumer) = spsc::<DropCounter>(4);
+            producer.push(DropCounter(Shared::clone(&drops)));
+            producer.push(DropCounter(Shared::clone(&drops)));
+            drop(consumer);
+            drop(producer);
+        }
+
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_interleaved_push_and_pop_preserves_capacity_bound() {
+        let (producer, mut consumer) = spsc::<u32>(2);
+
+        producer.push(1);
+        producer.push(2);
+        assert_eq!(consumer.pop(), Some(1));
+        producer.push(3);
+        producer.push(4);
+
+        assert_eq!(consumer.pop(), Some(3));
+        assert_eq!(consumer.pop(), Some(4));
+        assert_eq!(consumer.pop(), None);
+    }
+}
@@ -1,7 +1,9 @@
 use crate::config::Config;
+use crate::logging::{LogBuffer, LogLevel};
 use crate::types::*;
+use crate::ui::Theme;
 use chrono::Utc;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 
 #[derive(Debug, Clone)]
 pub struct AppState {
@@ -11,9 +13,16 @@ pub struct AppState {
     pub orderbook: Option<OrderBook>,
     pub fills: VecDeque<Fill>,
     pub signals: VecDeque<Signal>,
+    pub candles: VecDeque<Candle>,
     pub alerts: VecDeque<Alert>,
+    pub symbols: HashMap<String, Symbol>,
     pub connection_status: ConnectionStatus,
     pub connection_latency_ms: u64,
+    /// Rolling history of `connection_latency_ms` samples, oldest first,
+    /// bounded to `LATENCY_HISTORY_LEN`. Backs the status bar's inline
+    /// sparkline and min/avg/max/jitter readout.
+    pub latency_history: VecDeque<u64>,
+    pub capabilities: Option<ServerCapabilities>,
     pub last_update: chrono::DateTime<Utc>,
     pub focused_panel: Panel,
     pub input_mode: InputMode,
@@ -21,9 +30,38 @@ pub struct AppState {
     pub show_help: bool,
     pub selected_index: usize,
     pub scroll_offset: usize,
+    pub theme: Theme,
+    /// Whether glyph-heavy panels may use Unicode symbols, or should fall
+    /// back to their plain-ASCII equivalents (see `config::UiConfig`).
+    pub enhanced_graphics: bool,
     pub dirty: DirtyFlags,
+    /// Result of the most recently submitted command-mode/keybinding order
+    /// action (e.g. `:buy ...`, `c` to cancel the selected order), shown in
+    /// the command-mode status line until the next action replaces it.
+    pub action_feedback: Option<(AlertLevel, String)>,
+    /// Rolling `signal.strength` history per `(strategy, symbol)`, used to
+    /// render the Signals panel's trend sparkline. Bounded to
+    /// `SIGNAL_STRENGTH_HISTORY_LEN` samples per key independent of
+    /// `max_signals_display`, so the sparkline keeps trend context even
+    /// after older signals scroll out of `signals`.
+    pub signal_strength_history: HashMap<(String, String), VecDeque<f64>>,
+    /// Shared with the `logging::LogLayer` registered at startup, so every
+    /// `tracing` call elsewhere in the app feeds the Logs panel.
+    pub logs: LogBuffer,
+    /// Records below this level are hidden from the Logs panel. Cycled with
+    /// a keybind rather than configured, since it's meant to be adjusted
+    /// live while chasing down what's happening right now.
+    pub log_min_level: LogLevel,
 }
 
+/// Number of trailing `signal.strength` samples kept per `(strategy,
+/// symbol)` for the Signals panel sparkline.
+pub const SIGNAL_STRENGTH_HISTORY_LEN: usize = 32;
+
+/// Number of trailing `connection_latency_ms` samples kept for the status
+/// bar's sparkline and min/avg/max/jitter readout.
+pub const LATENCY_HISTORY_LEN: usize = 30;
+
 #[derive(Debug, Clone, Default)]
 pub struct DirtyFlags {
     pub positions: bool,
@@ -31,6 +69,7 @@ pub struct DirtyFlags {
     pub orderbook: bool,
     pub fills: bool,
     pub signals: bool,
+    pub candles: bool,
     pub alerts: bool,
     pub status: bool,
     pub all: bool,
@@ -43,12 +82,17 @@ pub enum StateUpdate {
     OrderBook(OrderBook),
     Fills(Vec<Fill>),
     Signals(Vec<Signal>),
+    Candles(Vec<Candle>),
     Alerts(Vec<Alert>),
+    Symbols(Vec<Symbol>),
     ConnectionStatus(ConnectionStatus, u64),
+    Capabilities(ServerCapabilities),
 }
 
 impl AppState {
-    pub fn new(config: Config) -> Self {
+    pub fn new(config: Config, logs: LogBuffer) -> Self {
+        let theme = Theme::load(&config.ui.color_scheme);
+        let enhanced_graphics = config.ui.enhanced_graphics;
         Self {
             config,
             positions: Vec::new(),
@@ -56,9 +100,13 @@ impl AppState {
             orderbook: None,
             fills: VecDeque::new(),
             signals: VecDeque::new(),
+            candles: VecDeque::new(),
             alerts: VecDeque::new(),
+            symbols: HashMap::new(),
             connection_status: ConnectionStatus::Disconnected,
             connection_latency_ms: 0,
+            latency_history: VecDeque::new(),
+            capabilities: None,
             last_update: Utc::now(),
             focused_panel: Panel::Positions,
             input_mode: InputMode::Normal,
@@ -66,7 +114,13 @@ impl AppState {
             show_help: false,
             selected_index: 0,
             scroll_offset: 0,
+            theme,
+            enhanced_graphics,
             dirty: DirtyFlags::default(),
+            action_feedback: None,
+            signal_strength_history: HashMap::new(),
+            logs,
+            log_min_level: LogLevel::Info,
         }
     }
 
@@ -81,7 +135,8 @@ impl AppState {
                     self.dirty.all = true;
                 }
             }
-            StateUpdate::Orders(orders) => {
+            StateUpdate::Orders(mut orders) => {
+                cancel_oco_siblings(&mut orders);
                 if self.orders != orders {
                     self.orders = orders;
                     self.dirty.orders = true;
@@ -95,43 +150,138 @@ impl AppState {
             }
             StateUpdate::Fills(fills) => {
                 for fill in fills {
-                    self.fills.push_front(fill);
-                }
-                while self.fills.len() > self.config.panels.max_fills_display {
-                    self.fills.pop_back();
+                    self.push_fill(fill);
                 }
                 self.dirty.fills = true;
                 self.dirty.all = true;
             }
             StateUpdate::Signals(signals) => {
                 for signal in signals {
-                    self.signals.push_front(signal);
-                }
-                while self.signals.len() > self.config.panels.max_signals_display {
-                    self.signals.pop_back();
+                    self.push_signal(signal);
                 }
                 self.dirty.signals = true;
                 self.dirty.all = true;
             }
+            StateUpdate::Candles(candles) => {
+                for candle in candles {
+                    match self.candles.iter_mut().find(|c| {
+                        c.symbol == candle.symbol
+                            && c.resolution == candle.resolution
+                            && c.open_time == candle.open_time
+                    }) {
+                        Some(existing) => *existing = candle,
+                        None => self.candles.push_front(candle),
+                    }
+                }
+                while self.candles.len() > self.config.panels.max_candles_display {
+                    self.candles.pop_back();
+                }
+                self.dirty.candles = true;
+                self.dirty.all = true;
+            }
             StateUpdate::Alerts(alerts) => {
                 for alert in alerts {
-                    self.alerts.push_front(alert);
-                }
-                while self.alerts.len() > self.config.panels.max_alerts_display {
-                    self.alerts.pop_back();
+                    self.push_alert(alert);
                 }
                 self.dirty.alerts = true;
                 self.dirty.all = true;
             }
+            StateUpdate::Symbols(symbols) => {
+                for symbol in symbols {
+                    self.symbols.insert(symbol.name.clone(), symbol);
+                }
+                self.dirty.all = true;
+            }
             StateUpdate::ConnectionStatus(status, latency) => {
                 self.connection_status = status;
                 self.connection_latency_ms = latency;
+                if status == ConnectionStatus::Connected {
+                    self.latency_history.push_back(latency);
+                    while self.latency_history.len() > LATENCY_HISTORY_LEN {
+                        self.latency_history.pop_front();
+                    }
+                }
+                self.dirty.status = true;
+                self.dirty.all = true;
+            }
+            StateUpdate::Capabilities(capabilities) => {
+                self.capabilities = Some(capabilities);
                 self.dirty.status = true;
                 self.dirty.all = true;
             }
         }
     }
 
+    fn push_fill(&mut self, fill: Fill) {
+        self.fills.push_front(fill);
+        while self.fills.len() > self.config.panels.max_fills_display {
+            self.fills.pop_back();
+        }
+    }
+
+    fn push_signal(&mut self, signal: Signal) {
+        let history = self
+            .signal_strength_history
+            .entry((signal.strategy.clone(), signal.symbol.clone()))
+            .or_default();
+        history.push_back(signal.strength);
+        while history.len() > SIGNAL_STRENGTH_HISTORY_LEN {
+            history.pop_front();
+        }
+
+        self.signals.push_front(signal);
+        while self.signals.len() > self.config.panels.max_signals_display {
+            self.signals.pop_back();
+        }
+    }
+
+    fn push_alert(&mut self, alert: Alert) {
+        self.alerts.push_front(alert);
+        while self.alerts.len() > self.config.panels.max_alerts_display {
+            self.alerts.pop_back();
+        }
+    }
+
+    /// Drains whatever the ingest side has pushed onto the lock-free fills
+    /// ring since the last drain, without the ring itself ever touching
+    /// this state's lock. Called once per render tick rather than once per
+    /// message, so bursty ingestion doesn't contend with the renderer.
+    pub fn drain_fills(&mut self, consumer: &mut crate::ring::Consumer<Fill>) {
+        let mut drained = false;
+        while let Some(fill) = consumer.pop() {
+            self.push_fill(fill);
+            drained = true;
+        }
+        if drained {
+            self.dirty.fills = true;
+            self.dirty.all = true;
+        }
+    }
+
+    pub fn drain_signals(&mut self, consumer: &mut crate::ring::Consumer<Signal>) {
+        let mut drained = false;
+        while let Some(signal) = consumer.pop() {
+            self.push_signal(signal);
+            drained = true;
+        }
+        if drained {
+            self.dirty.signals = true;
+            self.dirty.all = true;
+        }
+    }
+
+    pub fn drain_alerts(&mut self, consumer: &mut crate::ring::Consumer<Alert>) {
+        let mut drained = false;
+        while let Some(alert) = consumer.pop() {
+            self.push_alert(alert);
+            drained = true;
+        }
+        if drained {
+            self.dirty.alerts = true;
+            self.dirty.all = true;
+        }
+    }
+
     pub fn clear_dirty(&mut self) {
         self.dirty = DirtyFlags::default();
     }
@@ -155,9 +305,33 @@ impl AppState {
         self.dirty.all = true;
     }
 
+    /// Cycles the Logs panel's minimum-level filter.
+    pub fn cycle_log_min_level(&mut self) {
+        self.log_min_level = self.log_min_level.cycle();
+        self.dirty.all = true;
+    }
+
+    /// Live-switches the color scheme, e.g. from the `:theme <name>`
+    /// command. Falls back to the built-in default if `name` doesn't match
+    /// a file under `themes/`.
+    pub fn set_theme(&mut self, name: &str) {
+        self.theme = Theme::load(name);
+        self.dirty.all = true;
+    }
+
     pub fn enter_command_mode(&mut self) {
         self.input_mode = InputMode::Command;
         self.command_buffer.clear();
+        self.action_feedback = None;
+        self.dirty.all = true;
+    }
+
+    /// Records the outcome of an order action (submit/cancel/close) so the
+    /// command-mode status line reflects confirmed acks/rejects instead of
+    /// only surfacing failures through the general alerts panel.
+    pub fn set_action_feedback(&mut self, level: AlertLevel, message: String) {
+        self.action_feedback = Some((level, message));
+        self.dirty.status = true;
         self.dirty.all = true;
     }
 
@@ -177,6 +351,19 @@ impl AppState {
         self.dirty.all = true;
     }
 
+    /// Inserts or updates a single order by id, used when the execution
+    /// client acks an order locally rather than waiting for the next full
+    /// orders snapshot from the dashboard.
+    pub fn upsert_order(&mut self, order: Order) {
+        match self.orders.iter_mut().find(|o| o.id == order.id) {
+            Some(existing) => *existing = order,
+            None => self.orders.push(order),
+        }
+        cancel_oco_siblings(&mut self.orders);
+        self.dirty.orders = true;
+        self.dirty.all = true;
+    }
+
     pub fn get_selected_order_id(&self) -> Option<String> {
         if self.focused_panel == Panel::Orders && self.selected_index < self.orders.len() {
             Some(self.orders[self.selected_index].id.clone())
@@ -206,7 +393,9 @@ impl AppState {
             Panel::Orders => self.orders.len().saturating_sub(1),
             Panel::Fills => self.fills.len().saturating_sub(1),
             Panel::Signals => self.signals.len().saturating_sub(1),
+            Panel::Candles => self.candles.len().saturating_sub(1),
             Panel::Alerts => self.alerts.len().saturating_sub(1),
+            Panel::Logs => self.logs.len().saturating_sub(1),
             _ => 0,
         };
 
@@ -219,7 +408,25 @@ impl AppState {
     pub fn is_stale(&self) -> bool {
         let elapsed = Utc::now()
             .signed_duration_since(self.last_update)
-            .num_seconds();
-        elapsed > self.config.ui.stale_data_threshold_s as i64
+            .num_milliseconds();
+        elapsed > self.config.ui.stale_threshold_ms as i64
+    }
+}
+
+/// When an OCO leg reaches a terminal state, cancels its linked sibling
+/// locally instead of waiting for the venue to confirm the cancellation.
+fn cancel_oco_siblings(orders: &mut [Order]) {
+    let terminated_siblings: Vec<String> = orders
+        .iter()
+        .filter(|o| matches!(o.status, OrderStatus::Filled | OrderStatus::Canceled))
+        .filter_map(|o| o.linked_order_id.clone())
+        .collect();
+
+    for sibling_id in terminated_siblings {
+        if let Some(sibling) = orders.iter_mut().find(|o| o.id == sibling_id) {
+            if !matches!(sibling.status, OrderStatus::Filled | OrderStatus::Canceled) {
+                sibling.status = OrderStatus::Canceled;
+            }
+        }
     }
 }
@@ -0,0 +1,51 @@
+//! Terminal restoration shared between the happy path and panics.
+//!
+//! A panic while raw mode and the alternate screen are active leaves the
+//! user's shell garbled -- and the backtrace unreadable -- until they run
+//! `reset`. `install_panic_hook` restores the terminal first and then
+//! chains to whatever hook was previously installed, so the original panic
+//! message still prints normally. `TerminalGuard` performs the same
+//! teardown on `Drop`, covering `run_app`'s early-return error paths.
+//!
+//! Both go through `backend::TerminalBackend::restore`, which is itself
+//! idempotent (see each backend module), so whichever of the guard or the
+//! hook runs first does the work and the other is a no-op -- and neither
+//! one here needs to know which backend is compiled in.
+
+use crate::backend::{Active, TerminalBackend};
+
+/// Installs a panic hook that restores the terminal before printing the
+/// original panic message, then chains to whatever hook was previously
+/// installed. Call once at startup.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        Active::restore();
+        previous(info);
+    }));
+}
+
+/// RAII guard that restores the terminal on `Drop`. Hold one for the
+/// lifetime of the alternate-screen session so every early return out of
+/// `run_app` still leaves the shell usable; idempotent with the panic
+/// hook's teardown.
+#[must_use]
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TerminalGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        Active::restore();
+    }
+}
@@ -1,14 +1,15 @@
+use common::Decimal;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Position {
     pub symbol: String,
-    pub size: f64,
-    pub entry_price: f64,
-    pub current_price: f64,
+    pub size: Decimal,
+    pub entry_price: Decimal,
+    pub current_price: Decimal,
     pub side: PositionSide,
-    pub unrealized_pnl: f64,
+    pub unrealized_pnl: Decimal,
     pub pnl_percent: f64,
 }
 
@@ -24,11 +25,16 @@ pub struct Order {
     pub symbol: String,
     pub side: OrderSide,
     pub order_type: OrderType,
-    pub price: f64,
-    pub size: f64,
-    pub filled_size: f64,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub filled_size: Decimal,
     pub status: OrderStatus,
     pub timestamp: DateTime<Utc>,
+    /// For an OCO leg, the id of the other leg in the pair. When this order
+    /// transitions to `Filled`/`Canceled`, the state layer cancels the
+    /// sibling locally rather than waiting for the venue to confirm it.
+    #[serde(default)]
+    pub linked_order_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -43,6 +49,16 @@ pub enum OrderType {
     Market,
     StopLimit,
     StopMarket,
+    /// One-cancels-the-other bracket. Submitted as two linked `Order`s (a
+    /// stop leg and a limit leg) that reference each other via
+    /// `Order::linked_order_id`.
+    Oco { stop_price: Decimal, limit_price: Decimal },
+    /// Only `visible_qty` of `Order::size` is shown in the book; the rest
+    /// refills as the visible slice fills.
+    Iceberg { visible_qty: Decimal },
+    /// Stop price trails the market by `callback_rate` (as a fraction, e.g.
+    /// `0.01` for 1%) and triggers a market order once touched.
+    TrailingStop { callback_rate: Decimal },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -57,40 +73,40 @@ pub enum OrderStatus {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderBook {
     pub symbol: String,
-    pub bids: Vec<(f64, f64)>, // (price, size)
-    pub asks: Vec<(f64, f64)>, // (price, size)
+    pub bids: Vec<(Decimal, Decimal)>, // (price, size)
+    pub asks: Vec<(Decimal, Decimal)>, // (price, size)
     pub timestamp: DateTime<Utc>,
 }
 
 impl OrderBook {
-    pub fn spread(&self) -> f64 {
+    pub fn spread(&self) -> Decimal {
         if let (Some(best_bid), Some(best_ask)) = (
             self.bids.first().map(|b| b.0),
             self.asks.first().map(|a| a.0),
         ) {
             best_ask - best_bid
         } else {
-            0.0
+            Decimal::zero()
         }
     }
 
-    pub fn mid_price(&self) -> f64 {
+    pub fn mid_price(&self) -> Decimal {
         if let (Some(best_bid), Some(best_ask)) = (
             self.bids.first().map(|b| b.0),
             self.asks.first().map(|a| a.0),
         ) {
-            (best_ask + best_bid) / 2.0
+            (best_ask + best_bid) / Decimal::from_i64(2)
         } else {
-            0.0
+            Decimal::zero()
         }
     }
 
-    pub fn spread_percent(&self) -> f64 {
+    pub fn spread_percent(&self) -> Decimal {
         let mid = self.mid_price();
-        if mid > 0.0 {
-            (self.spread() / mid) * 100.0
+        if mid.is_positive() && !mid.is_zero() {
+            (self.spread() / mid) * Decimal::from_i64(100)
         } else {
-            0.0
+            Decimal::zero()
         }
     }
 }
@@ -101,10 +117,10 @@ pub struct Fill {
     pub order_id: String,
     pub symbol: String,
     pub side: OrderSide,
-    pub price: f64,
-    pub size: f64,
-    pub fee: f64,
-    pub pnl: f64,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub fee: Decimal,
+    pub pnl: Decimal,
     pub timestamp: DateTime<Utc>,
 }
 
@@ -126,6 +142,21 @@ pub enum SignalType {
     Neutral,
 }
 
+/// A single OHLCV bar for `symbol` at `resolution` (e.g. "1m", "1h"),
+/// covering `[open_time, open_time + resolution)`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Candle {
+    pub symbol: String,
+    pub resolution: String,
+    pub open_time: DateTime<Utc>,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub base_volume: Decimal,
+    pub quote_volume: Decimal,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Alert {
     pub id: String,
@@ -149,6 +180,35 @@ pub enum ConnectionStatus {
     Error,
 }
 
+/// Capability set negotiated with the dashboard server during the
+/// handshake. Which panels/order types the UI can use is derived from the
+/// server's advertised `protocol_version`/`feature_version` rather than
+/// assumed, so an older server degrades gracefully instead of sending
+/// messages the client doesn't understand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerCapabilities {
+    pub protocol_version: u32,
+    pub feature_version: u32,
+}
+
+impl ServerCapabilities {
+    /// The signals channel was introduced in protocol v2.
+    pub fn supports_signals(&self) -> bool {
+        self.protocol_version >= 2
+    }
+
+    /// OCO/Iceberg/TrailingStop order types require protocol v2 with
+    /// feature v1 or later.
+    pub fn supports_oco(&self) -> bool {
+        self.protocol_version >= 2 && self.feature_version >= 1
+    }
+
+    /// The candles channel requires protocol v2 with feature v2 or later.
+    pub fn supports_candles(&self) -> bool {
+        self.protocol_version >= 2 && self.feature_version >= 2
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Panel {
     Positions,
@@ -156,7 +216,9 @@ pub enum Panel {
     Fills,
     OrderBook,
     Signals,
+    Candles,
     Alerts,
+    Logs,
 }
 
 impl Panel {
@@ -166,19 +228,23 @@ impl Panel {
             Panel::Orders => Panel::Fills,
             Panel::Fills => Panel::OrderBook,
             Panel::OrderBook => Panel::Signals,
-            Panel::Signals => Panel::Alerts,
-            Panel::Alerts => Panel::Positions,
+            Panel::Signals => Panel::Candles,
+            Panel::Candles => Panel::Alerts,
+            Panel::Alerts => Panel::Logs,
+            Panel::Logs => Panel::Positions,
         }
     }
 
     pub fn prev(&self) -> Self {
         match self {
-            Panel::Positions => Panel::Alerts,
+            Panel::Positions => Panel::Logs,
             Panel::Orders => Panel::Positions,
             Panel::Fills => Panel::Orders,
             Panel::OrderBook => Panel::Fills,
             Panel::Signals => Panel::OrderBook,
-            Panel::Alerts => Panel::Signals,
+            Panel::Candles => Panel::Signals,
+            Panel::Alerts => Panel::Candles,
+            Panel::Logs => Panel::Alerts,
         }
     }
 }
@@ -188,3 +254,116 @@ pub enum InputMode {
     Normal,
     Command,
 }
+
+/// Exchange-published trading rules for a single symbol, e.g. lot size and
+/// tick size constraints that an order must satisfy to be accepted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Symbol {
+    pub name: String,
+    pub base_asset_precision: u32,
+    pub quote_precision: u32,
+    pub filters: Vec<SymbolFilter>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SymbolFilter {
+    LotSize {
+        min_qty: f64,
+        max_qty: f64,
+        step_size: f64,
+    },
+    PriceFilter {
+        min_price: f64,
+        max_price: f64,
+        tick_size: f64,
+    },
+    MinNotional {
+        min_notional: f64,
+    },
+}
+
+/// Why an order was rejected against a symbol's filters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderRejectReason {
+    PriceTooLow,
+    PriceTooHigh,
+    QtyTooLow,
+    QtyTooHigh,
+    BelowMinNotional,
+}
+
+impl Symbol {
+    fn lot_size(&self) -> Option<(f64, f64, f64)> {
+        self.filters.iter().find_map(|f| match f {
+            SymbolFilter::LotSize {
+                min_qty,
+                max_qty,
+                step_size,
+            } => Some((*min_qty, *max_qty, *step_size)),
+            _ => None,
+        })
+    }
+
+    fn price_filter(&self) -> Option<(f64, f64, f64)> {
+        self.filters.iter().find_map(|f| match f {
+            SymbolFilter::PriceFilter {
+                min_price,
+                max_price,
+                tick_size,
+            } => Some((*min_price, *max_price, *tick_size)),
+            _ => None,
+        })
+    }
+
+    fn min_notional(&self) -> Option<f64> {
+        self.filters.iter().find_map(|f| match f {
+            SymbolFilter::MinNotional { min_notional } => Some(*min_notional),
+            _ => None,
+        })
+    }
+
+    /// Snaps a price down to the nearest `tick_size`, if a `PriceFilter` is set.
+    pub fn round_price(&self, p: f64) -> f64 {
+        match self.price_filter() {
+            Some((_, _, tick_size)) if tick_size > 0.0 => (p / tick_size).floor() * tick_size,
+            _ => p,
+        }
+    }
+
+    /// Snaps a quantity down to the nearest `step_size`, if a `LotSize` filter is set.
+    pub fn round_qty(&self, q: f64) -> f64 {
+        match self.lot_size() {
+            Some((_, _, step_size)) if step_size > 0.0 => (q / step_size).floor() * step_size,
+            _ => q,
+        }
+    }
+
+    /// Validates a price/size pair against this symbol's filters.
+    pub fn validate_order(&self, price: f64, size: f64) -> Result<(), OrderRejectReason> {
+        if let Some((min_price, max_price, _)) = self.price_filter() {
+            if price < min_price {
+                return Err(OrderRejectReason::PriceTooLow);
+            }
+            if max_price > 0.0 && price > max_price {
+                return Err(OrderRejectReason::PriceTooHigh);
+            }
+        }
+
+        if let Some((min_qty, max_qty, _)) = self.lot_size() {
+            if size < min_qty {
+                return Err(OrderRejectReason::QtyTooLow);
+            }
+            if max_qty > 0.0 && size > max_qty {
+                return Err(OrderRejectReason::QtyTooHigh);
+            }
+        }
+
+        if let Some(min_notional) = self.min_notional() {
+            if price * size < min_notional {
+                return Err(OrderRejectReason::BelowMinNotional);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,5 @@
 use crate::state::AppState;
 use crate::types::Panel;
-use crate::ui::theme::Theme;
 use chrono::Utc;
 use ratatui::{
     layout::Rect,
@@ -11,7 +10,7 @@ use ratatui::{
 };
 
 pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
-    let theme = Theme::default();
+    let theme = &state.theme;
 
     let is_focused = state.focused_panel == Panel::Alerts;
     let border_style = if is_focused {
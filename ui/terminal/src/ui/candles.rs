@@ -0,0 +1,141 @@
+use crate::state::AppState;
+use crate::types::Panel;
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, BorderType, Paragraph},
+    Frame,
+};
+use ratatui::layout::Rect;
+
+pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
+    let theme = &state.theme;
+
+    let is_focused = state.focused_panel == Panel::Candles;
+    let border_style = if is_focused {
+        Style::default().fg(theme.border_focused)
+    } else {
+        Style::default().fg(theme.border)
+    };
+
+    let supports_candles = state
+        .capabilities
+        .map(|c| c.supports_candles())
+        .unwrap_or(true);
+
+    if !supports_candles {
+        let paragraph = Paragraph::new(vec![Line::from(vec![Span::styled(
+            "Candles unavailable: server protocol is too old for this channel",
+            Style::default().fg(theme.text_dim),
+        )])])
+        .block(
+            Block::default()
+                .title(" CANDLES (disabled) ")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(border_style),
+        );
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("{:<6}", "Res"),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("{:<10}", "Symbol"),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("{:>10}", "Open"),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("{:>10}", "High"),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("{:>10}", "Low"),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("{:>10}", "Close"),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("{:>12}", "Volume"),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+    ])];
+
+    if state.candles.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            "No candles yet",
+            Style::default().fg(theme.text_dim),
+        )]));
+    } else {
+        for (idx, candle) in state.candles.iter().enumerate() {
+            let candle_style = if candle.close >= candle.open {
+                Style::default().fg(theme.buy)
+            } else {
+                Style::default().fg(theme.sell)
+            };
+
+            let mut line_style = Style::default().fg(theme.text);
+            if is_focused && idx == state.selected_index {
+                line_style = line_style
+                    .bg(theme.border_focused)
+                    .add_modifier(Modifier::BOLD);
+            }
+
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<6}", candle.resolution), line_style),
+                Span::raw(" "),
+                Span::styled(format!("{:<10}", candle.symbol), line_style),
+                Span::raw(" "),
+                Span::styled(
+                    format!("{:>10}", candle.open.to_string_with_scale(2)),
+                    line_style,
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    format!("{:>10}", candle.high.to_string_with_scale(2)),
+                    line_style,
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    format!("{:>10}", candle.low.to_string_with_scale(2)),
+                    line_style,
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    format!("{:>10}", candle.close.to_string_with_scale(2)),
+                    candle_style,
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    format!("{:>12}", candle.base_volume.to_string_with_scale(4)),
+                    line_style,
+                ),
+            ]));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(" CANDLES ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(border_style),
+    );
+
+    f.render_widget(paragraph, area);
+}
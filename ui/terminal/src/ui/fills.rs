@@ -1,6 +1,6 @@
 use crate::state::AppState;
 use crate::types::Panel;
-use crate::ui::theme::Theme;
+use crate::ui::format::signed_decimal_string;
 use chrono::Utc;
 use ratatui::{
     layout::Rect,
@@ -11,7 +11,7 @@ use ratatui::{
 };
 
 pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
-    let theme = Theme::default();
+    let theme = &state.theme;
 
     let is_focused = state.focused_panel == Panel::Fills;
     let border_style = if is_focused {
@@ -81,7 +81,7 @@ pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
             };
 
             let side_style = theme.order_side_style(&fill.side);
-            let pnl_style = theme.profit_style(fill.pnl);
+            let pnl_style = theme.profit_style(fill.pnl.to_f64().unwrap_or(0.0));
             let mut line_style = Style::default().fg(theme.text);
 
             if is_focused && idx == state.selected_index {
@@ -97,13 +97,16 @@ pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
                 Span::raw(" "),
                 Span::styled(format!("{:<4}", side_text), side_style),
                 Span::raw(" "),
-                Span::styled(format!("{:>10.2}", fill.price), line_style),
+                Span::styled(format!("{:>10}", fill.price.to_string_with_scale(2)), line_style),
                 Span::raw(" "),
-                Span::styled(format!("{:>8.4}", fill.size), line_style),
+                Span::styled(format!("{:>8}", fill.size.to_string_with_scale(4)), line_style),
                 Span::raw(" "),
-                Span::styled(format!("{:>8.4}", fill.fee), line_style),
+                Span::styled(format!("{:>8}", fill.fee.to_string_with_scale(4)), line_style),
                 Span::raw(" "),
-                Span::styled(format!("{:+>10.2}", fill.pnl), pnl_style),
+                Span::styled(
+                    format!("{:>10}", signed_decimal_string(&fill.pnl, 2)),
+                    pnl_style,
+                ),
             ]));
         }
     }
@@ -0,0 +1,11 @@
+use common::Decimal;
+
+/// Formats a Decimal with an explicit leading sign, for the always-signed
+/// P&L columns shown in the positions and fills panels.
+pub fn signed_decimal_string(value: &Decimal, scale: u32) -> String {
+    if value.is_negative() {
+        value.to_string_with_scale(scale)
+    } else {
+        format!("+{}", value.to_string_with_scale(scale))
+    }
+}
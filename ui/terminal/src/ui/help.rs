@@ -1,5 +1,4 @@
 use crate::state::AppState;
-use crate::ui::theme::Theme;
 use ratatui::{
     layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
@@ -8,8 +7,8 @@ use ratatui::{
     Frame,
 };
 
-pub fn render(f: &mut Frame, area: Rect, _state: &AppState) {
-    let theme = Theme::default();
+pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
+    let theme = &state.theme;
 
     // Create a centered popup
     let popup_area = centered_rect(60, 70, area);
@@ -43,6 +42,12 @@ pub fn render(f: &mut Frame, area: Rect, _state: &AppState) {
         Line::from("  j / Down     Scroll down"),
         Line::from("  k / Up       Scroll up"),
         Line::from(""),
+        Line::from(vec![Span::styled(
+            "Panel-Specific (Logs):",
+            Style::default().add_modifier(Modifier::BOLD),
+        )]),
+        Line::from("  l            Cycle minimum log level filter"),
+        Line::from(""),
         Line::from(vec![Span::styled(
             "Panel-Specific (Orders):",
             Style::default().add_modifier(Modifier::BOLD),
@@ -61,11 +66,12 @@ pub fn render(f: &mut Frame, area: Rect, _state: &AppState) {
             "Command Mode:",
             Style::default().add_modifier(Modifier::BOLD),
         )]),
-        Line::from("  :buy <symbol> <size> <price>    Place limit buy order"),
-        Line::from("  :sell <symbol> <size> <price>   Place limit sell order"),
-        Line::from("  :market <side> <symbol> <size>  Place market order"),
-        Line::from("  :cancel <order_id>              Cancel specific order"),
-        Line::from("  :close <symbol>                 Close position"),
+        Line::from("  :buy <symbol> <size> @ <price> [type]   Place buy order"),
+        Line::from("  :sell <symbol> <size> @ <price> [type]  Place sell order"),
+        Line::from("  :market <side> <symbol> <size>          Place market order"),
+        Line::from("  :cancel <order_id>                      Cancel specific order"),
+        Line::from("  :close <symbol>                         Close position"),
+        Line::from("  :theme <name>                           Switch color scheme"),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Press ? to close this help screen",
@@ -0,0 +1,73 @@
+use crate::state::AppState;
+use crate::types::Panel;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
+    let theme = &state.theme;
+
+    let is_focused = state.focused_panel == Panel::Logs;
+    let border_style = if is_focused {
+        Style::default().fg(theme.border_focused)
+    } else {
+        Style::default().fg(theme.border)
+    };
+
+    let records = state.logs.snapshot();
+    let min_level = state.log_min_level;
+    let filtered: Vec<_> = records
+        .iter()
+        .rev() // newest first, matching the other list panels
+        .filter(|record| record.level >= min_level)
+        .collect();
+
+    let mut lines = Vec::new();
+    if filtered.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            "No log records at or above the current filter",
+            Style::default().fg(theme.text_dim),
+        )]));
+    } else {
+        for (idx, record) in filtered.iter().enumerate() {
+            let level_style = theme.log_level_style(&record.level);
+            let mut line_style = Style::default().fg(theme.text);
+
+            if is_focused && idx == state.selected_index {
+                line_style = line_style
+                    .bg(theme.border_focused)
+                    .add_modifier(Modifier::BOLD);
+            }
+
+            lines.push(Line::from(vec![
+                Span::styled(
+                    record.timestamp.format("%H:%M:%S").to_string(),
+                    Style::default().fg(theme.text_dim),
+                ),
+                Span::raw(" "),
+                Span::styled(format!("{:<5}", record.level.label()), level_style),
+                Span::raw(" "),
+                Span::styled(
+                    format!("{:<16}", record.target),
+                    Style::default().fg(theme.text_dim),
+                ),
+                Span::raw(" "),
+                Span::styled(record.message.clone(), line_style),
+            ]));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" LOGS [min: {}] ", min_level.label()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(border_style),
+    );
+
+    f.render_widget(paragraph, area);
+}
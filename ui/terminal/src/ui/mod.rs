@@ -14,7 +14,10 @@ mod orders;
 mod fills;
 mod orderbook;
 mod signals;
+mod candles;
 mod alerts;
+mod logs;
+mod format;
 
 pub use theme::Theme;
 
@@ -71,14 +74,21 @@ fn render_main_content(f: &mut Frame, area: Rect, state: &AppState) {
     orders::render(f, left_panels[1], state);
     fills::render(f, left_panels[2], state);
 
-    // Right column: OrderBook, Signals
+    // Right column: OrderBook, Signals, Candles, Logs
     let right_panels = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .constraints([
+            Constraint::Percentage(35),
+            Constraint::Percentage(20),
+            Constraint::Percentage(25),
+            Constraint::Percentage(20),
+        ])
         .split(columns[1]);
 
     orderbook::render(f, right_panels[0], state);
     signals::render(f, right_panels[1], state);
+    candles::render(f, right_panels[2], state);
+    logs::render(f, right_panels[3], state);
 }
 
 fn render_help_bar(f: &mut Frame, area: Rect, state: &AppState) {
@@ -88,15 +98,16 @@ fn render_help_bar(f: &mut Frame, area: Rect, state: &AppState) {
         widgets::Paragraph,
     };
 
-    let theme = Theme::default();
+    let theme = &state.theme;
 
     let help_text = if state.input_mode == crate::types::InputMode::Command {
         vec![
-            Span::styled("Enter", Style::default().fg(theme.highlight)),
-            Span::raw(" Execute | "),
-            Span::styled("Esc", Style::default().fg(theme.highlight)),
-            Span::raw(" Cancel"),
+            Span::styled(":", Style::default().fg(theme.highlight)),
+            Span::raw(&state.command_buffer),
+            Span::styled("_", Style::default().fg(theme.text_dim)),
         ]
+    } else if let Some((level, message)) = &state.action_feedback {
+        vec![Span::styled(message.clone(), theme.alert_level_style(level))]
     } else {
         vec![
             Span::styled("?", Style::default().fg(theme.highlight)),
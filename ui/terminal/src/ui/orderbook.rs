@@ -1,6 +1,7 @@
 use crate::state::AppState;
 use crate::types::Panel;
 use crate::ui::theme::Theme;
+use common::Decimal;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -10,7 +11,7 @@ use ratatui::{
 };
 
 pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
-    let theme = Theme::default();
+    let theme = &state.theme;
 
     let is_focused = state.focused_panel == Panel::OrderBook;
     let border_style = if is_focused {
@@ -41,9 +42,9 @@ pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
             ])
             .split(inner);
 
-        render_bids(f, columns[0], &orderbook.bids, &theme);
-        render_prices(f, columns[1], orderbook, &theme);
-        render_asks(f, columns[2], &orderbook.asks, &theme);
+        render_bids(f, columns[0], &orderbook.bids, theme);
+        render_prices(f, columns[1], orderbook, theme);
+        render_asks(f, columns[2], &orderbook.asks, theme);
     } else {
         let paragraph = Paragraph::new(vec![Line::from(vec![Span::styled(
             "No orderbook data",
@@ -61,8 +62,11 @@ pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
     }
 }
 
-fn render_bids(f: &mut Frame, area: Rect, bids: &[(f64, f64)], theme: &Theme) {
-    let max_size = bids.iter().map(|(_, s)| *s).fold(0.0f64, f64::max);
+fn render_bids(f: &mut Frame, area: Rect, bids: &[(Decimal, Decimal)], theme: &Theme) {
+    let max_size = bids
+        .iter()
+        .filter_map(|(_, s)| s.to_f64())
+        .fold(0.0f64, f64::max);
 
     let mut lines = vec![Line::from(vec![Span::styled(
         format!("{:>8} {:>10}", "Size", "Price"),
@@ -70,8 +74,9 @@ fn render_bids(f: &mut Frame, area: Rect, bids: &[(f64, f64)], theme: &Theme) {
     )])];
 
     for (price, size) in bids.iter().take(10) {
+        let size_f64 = size.to_f64().unwrap_or(0.0);
         let bar_length = if max_size > 0.0 {
-            ((*size / max_size) * 15.0) as usize
+            ((size_f64 / max_size) * 15.0) as usize
         } else {
             0
         };
@@ -79,14 +84,14 @@ fn render_bids(f: &mut Frame, area: Rect, bids: &[(f64, f64)], theme: &Theme) {
 
         lines.push(Line::from(vec![
             Span::styled(
-                format!("{:>8.4}", size),
+                format!("{:>8}", size.to_string_with_scale(4)),
                 Style::default().fg(theme.buy),
             ),
             Span::raw(" "),
             Span::styled(bar, Style::default().fg(theme.buy)),
             Span::raw(" "),
             Span::styled(
-                format!("{:>10.2}", price),
+                format!("{:>10}", price.to_string_with_scale(2)),
                 Style::default().fg(theme.text),
             ),
         ]));
@@ -119,21 +124,21 @@ fn render_prices(
         Line::from(vec![
             Span::styled("Spread: ", Style::default().fg(theme.text_dim)),
             Span::styled(
-                format!("{:.2}", spread),
+                spread.to_string_with_scale(2),
                 Style::default().fg(theme.warning),
             ),
         ]),
         Line::from(vec![
             Span::styled("      (", Style::default().fg(theme.text_dim)),
             Span::styled(
-                format!("{:.4}%", spread_pct),
+                format!("{}%", spread_pct.to_string_with_scale(4)),
                 Style::default().fg(theme.warning),
             ),
             Span::styled(")", Style::default().fg(theme.text_dim)),
         ]),
         Line::from(vec![
             Span::styled("Mid: ", Style::default().fg(theme.text_dim)),
-            Span::styled(format!("{:.2}", mid), Style::default().fg(theme.text)),
+            Span::styled(mid.to_string_with_scale(2), Style::default().fg(theme.text)),
         ]),
     ];
 
@@ -141,8 +146,11 @@ fn render_prices(
     f.render_widget(paragraph, area);
 }
 
-fn render_asks(f: &mut Frame, area: Rect, asks: &[(f64, f64)], theme: &Theme) {
-    let max_size = asks.iter().map(|(_, s)| *s).fold(0.0f64, f64::max);
+fn render_asks(f: &mut Frame, area: Rect, asks: &[(Decimal, Decimal)], theme: &Theme) {
+    let max_size = asks
+        .iter()
+        .filter_map(|(_, s)| s.to_f64())
+        .fold(0.0f64, f64::max);
 
     let mut lines = vec![Line::from(vec![Span::styled(
         format!("{:<10} {:<8}", "Price", "Size"),
@@ -150,8 +158,9 @@ fn render_asks(f: &mut Frame, area: Rect, asks: &[(f64, f64)], theme: &Theme) {
     )])];
 
     for (price, size) in asks.iter().take(10) {
+        let size_f64 = size.to_f64().unwrap_or(0.0);
         let bar_length = if max_size > 0.0 {
-            ((*size / max_size) * 15.0) as usize
+            ((size_f64 / max_size) * 15.0) as usize
         } else {
             0
         };
@@ -159,14 +168,14 @@ fn render_asks(f: &mut Frame, area: Rect, asks: &[(f64, f64)], theme: &Theme) {
 
         lines.push(Line::from(vec![
             Span::styled(
-                format!("{:<10.2}", price),
+                format!("{:<10}", price.to_string_with_scale(2)),
                 Style::default().fg(theme.text),
             ),
             Span::raw(" "),
             Span::styled(bar, Style::default().fg(theme.sell)),
             Span::raw(" "),
             Span::styled(
-                format!("{:<8.4}", size),
+                format!("{:<8}", size.to_string_with_scale(4)),
                 Style::default().fg(theme.sell),
             ),
         ]));
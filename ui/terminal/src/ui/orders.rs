@@ -1,6 +1,5 @@
 use crate::state::AppState;
-use crate::types::{OrderStatus, Panel};
-use crate::ui::theme::Theme;
+use crate::types::{Order, OrderStatus, Panel, Symbol};
 use ratatui::{
     layout::Rect,
     style::{Modifier, Style},
@@ -9,8 +8,80 @@ use ratatui::{
     Frame,
 };
 
+/// Number of digits after the decimal point implied by a filter step, e.g.
+/// `0.0100` implies 2 decimal places. Falls back to `default_scale` when the
+/// symbol carries no matching filter.
+fn step_scale(step: Option<f64>, default_scale: usize) -> usize {
+    match step {
+        Some(step) if step > 0.0 => format!("{step}")
+            .split('.')
+            .nth(1)
+            .map(|frac| frac.trim_end_matches('0').len())
+            .unwrap_or(0),
+        _ => default_scale,
+    }
+}
+
+fn price_scale(symbol: Option<&Symbol>) -> usize {
+    let tick = symbol.and_then(|s| {
+        s.filters.iter().find_map(|f| match f {
+            crate::types::SymbolFilter::PriceFilter { tick_size, .. } => Some(*tick_size),
+            _ => None,
+        })
+    });
+    step_scale(tick, 2)
+}
+
+fn qty_scale(symbol: Option<&Symbol>) -> usize {
+    let step = symbol.and_then(|s| {
+        s.filters.iter().find_map(|f| match f {
+            crate::types::SymbolFilter::LotSize { step_size, .. } => Some(*step_size),
+            _ => None,
+        })
+    });
+    step_scale(step, 4)
+}
+
+/// Describes the parts of an order's type/linkage that don't fit the
+/// fixed-width columns: the OCO sibling, and hidden vs. visible size for
+/// icebergs and trailing-stop callback rate. OCO prices are formatted at
+/// `price_scale`; iceberg quantities at `qty_scale`.
+fn order_type_annotation(order: &Order, price_scale: usize, qty_scale: usize) -> Option<String> {
+    match &order.order_type {
+        crate::types::OrderType::Oco { stop_price, limit_price } => Some(format!(
+            "OCO stop {} / limit {}{}",
+            stop_price.to_string_with_scale(price_scale as u32),
+            limit_price.to_string_with_scale(price_scale as u32),
+            order
+                .linked_order_id
+                .as_ref()
+                .map(|id| format!(" <-> {id}"))
+                .unwrap_or_default(),
+        )),
+        crate::types::OrderType::Iceberg { visible_qty } => Some(format!(
+            "iceberg: {} visible / {} hidden",
+            visible_qty.to_string_with_scale(qty_scale as u32),
+            (order.size - *visible_qty).to_string_with_scale(qty_scale as u32),
+        )),
+        crate::types::OrderType::TrailingStop { callback_rate } => Some(format!(
+            "trailing stop: {:.2}% callback",
+            callback_rate.to_f64().unwrap_or(0.0) * 100.0,
+        )),
+        _ => None,
+    }
+}
+
+fn violates_filters(order: &Order, symbol: Option<&Symbol>) -> bool {
+    let (Some(price), Some(size)) = (order.price.to_f64(), order.size.to_f64()) else {
+        return false;
+    };
+    symbol
+        .map(|s| s.validate_order(price, size).is_err())
+        .unwrap_or(false)
+}
+
 pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
-    let theme = Theme::default();
+    let theme = &state.theme;
 
     let is_focused = state.focused_panel == Panel::Orders;
     let border_style = if is_focused {
@@ -71,6 +142,7 @@ pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
                 OrderStatus::Rejected => "REJECTED",
             };
 
+            let symbol = state.symbols.get(&order.symbol);
             let side_style = theme.order_side_style(&order.side);
             let mut line_style = Style::default().fg(theme.text);
 
@@ -80,6 +152,21 @@ pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
                     .add_modifier(Modifier::BOLD);
             }
 
+            let price_style = if violates_filters(order, symbol) {
+                line_style.fg(theme.error)
+            } else {
+                line_style
+            };
+
+            let price_text = format!(
+                "{:>10}",
+                order.price.to_string_with_scale(price_scale(symbol) as u32)
+            );
+            let size_text = format!(
+                "{:>8}",
+                order.size.to_string_with_scale(qty_scale(symbol) as u32)
+            );
+
             lines.push(Line::from(vec![
                 Span::styled(format!("{:<12}", order.id), line_style),
                 Span::raw(" "),
@@ -87,12 +174,19 @@ pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
                 Span::raw(" "),
                 Span::styled(format!("{:<4}", side_text), side_style),
                 Span::raw(" "),
-                Span::styled(format!("{:>10.2}", order.price), line_style),
+                Span::styled(price_text, price_style),
                 Span::raw(" "),
-                Span::styled(format!("{:>8.4}", order.size), line_style),
+                Span::styled(size_text, price_style),
                 Span::raw(" "),
                 Span::styled(format!("{:<10}", status_text), line_style),
             ]));
+
+            if let Some(annotation) = order_type_annotation(order, price_scale(symbol), qty_scale(symbol)) {
+                lines.push(Line::from(vec![Span::styled(
+                    format!("  {annotation}"),
+                    Style::default().fg(theme.text_dim),
+                )]));
+            }
         }
     }
 
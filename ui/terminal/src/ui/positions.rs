@@ -1,6 +1,6 @@
 use crate::state::AppState;
 use crate::types::{Panel, PositionSide};
-use crate::ui::theme::Theme;
+use crate::ui::format::signed_decimal_string;
 use ratatui::{
     layout::Rect,
     style::{Modifier, Style},
@@ -10,7 +10,7 @@ use ratatui::{
 };
 
 pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
-    let theme = Theme::default();
+    let theme = &state.theme;
 
     let is_focused = state.focused_panel == Panel::Positions;
     let border_style = if is_focused {
@@ -63,7 +63,7 @@ pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
                 PositionSide::Short => "-",
             };
 
-            let pnl_style = theme.profit_style(position.unrealized_pnl);
+            let pnl_style = theme.profit_style(position.unrealized_pnl.to_f64().unwrap_or(0.0));
             let mut line_style = Style::default().fg(theme.text);
 
             if is_focused && idx == state.selected_index {
@@ -76,16 +76,25 @@ pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
                 Span::styled(format!("{:<10}", position.symbol), line_style),
                 Span::raw(" "),
                 Span::styled(
-                    format!("{}{:>7.4}", side_symbol, position.size.abs()),
+                    format!("{}{:>7}", side_symbol, position.size.abs().to_string_with_scale(4)),
                     line_style,
                 ),
                 Span::raw(" "),
-                Span::styled(format!("{:>10.2}", position.entry_price), line_style),
+                Span::styled(
+                    format!("{:>10}", position.entry_price.to_string_with_scale(2)),
+                    line_style,
+                ),
                 Span::raw(" "),
-                Span::styled(format!("{:>10.2}", position.current_price), line_style),
+                Span::styled(
+                    format!("{:>10}", position.current_price.to_string_with_scale(2)),
+                    line_style,
+                ),
                 Span::raw(" "),
                 Span::styled(
-                    format!("{:+>12.2}", position.unrealized_pnl),
+                    format!(
+                        "{:>12}",
+                        signed_decimal_string(&position.unrealized_pnl, 2)
+                    ),
                     pnl_style,
                 ),
                 Span::raw(" "),
@@ -1,17 +1,19 @@
 use crate::state::AppState;
 use crate::types::Panel;
-use crate::ui::theme::Theme;
 use chrono::Utc;
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, BorderType, Paragraph},
+    widgets::{Block, Borders, BorderType, Paragraph, Sparkline},
     Frame,
 };
 
+/// Width reserved for the trailing trend-sparkline column.
+const TREND_WIDTH: u16 = 10;
+
 pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
-    let theme = Theme::default();
+    let theme = &state.theme;
 
     let is_focused = state.focused_panel == Panel::Signals;
     let border_style = if is_focused {
@@ -20,6 +22,44 @@ pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
         Style::default().fg(theme.border)
     };
 
+    let supports_signals = state
+        .capabilities
+        .map(|c| c.supports_signals())
+        .unwrap_or(true);
+
+    if !supports_signals {
+        let paragraph = Paragraph::new(vec![Line::from(vec![Span::styled(
+            "Signals unavailable: server protocol is too old for this channel",
+            Style::default().fg(theme.text_dim),
+        )])])
+        .block(
+            Block::default()
+                .title(" AI SIGNALS (disabled) ")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(border_style),
+        );
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let block = Block::default()
+        .title(" AI SIGNALS ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(border_style);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(TREND_WIDTH)])
+        .split(inner);
+    let table_area = columns[0];
+    let trend_area = columns[1];
+
+    let trend_width = TREND_WIDTH as usize;
+
     let mut lines = vec![Line::from(vec![
         Span::styled(
             format!("{:<8}", "Time"),
@@ -52,11 +92,17 @@ pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
         ),
     ])];
 
+    // One sparkline widget per visible signal row, positioned over the
+    // trailing column reserved above; `None` for a signal with no history
+    // yet renders as an empty cell instead of a flat/zero sparkline.
+    let mut row_sparklines: Vec<Option<Vec<u64>>> = vec![None];
+
     if state.signals.is_empty() {
         lines.push(Line::from(vec![Span::styled(
             "No AI signals (placeholder)",
             Style::default().fg(theme.text_dim),
         )]));
+        row_sparklines.push(None);
     } else {
         for (idx, signal) in state.signals.iter().enumerate() {
             let signal_text = match signal.signal_type {
@@ -78,7 +124,7 @@ pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
 
             // Create strength bar (0.0 to 1.0)
             let bar_length = (signal.strength * 10.0) as usize;
-            let strength_bar = "█".repeat(bar_length);
+            let strength_bar = theme.strength_bar(bar_length, 10, state.enhanced_graphics);
 
             let signal_style = theme.signal_type_style(&signal.signal_type);
             let mut line_style = Style::default().fg(theme.text);
@@ -105,16 +151,40 @@ pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
                     line_style,
                 ),
             ]));
+
+            let key = (signal.strategy.clone(), signal.symbol.clone());
+            let samples = state.signal_strength_history.get(&key).map(|history| {
+                // Sparkline scales to the 0.0-1.0 strength range rather than
+                // its own data's max, so a lone low-strength sample still
+                // renders a short bar instead of a full one.
+                history
+                    .iter()
+                    .map(|s| (s.clamp(0.0, 1.0) * 100.0) as u64)
+                    .collect::<Vec<u64>>()
+            });
+            row_sparklines.push(samples);
         }
     }
 
-    let paragraph = Paragraph::new(lines).block(
-        Block::default()
-            .title(" AI SIGNALS ")
-            .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .border_style(border_style),
-    );
+    let paragraph = Paragraph::new(lines);
+    f.render_widget(paragraph, table_area);
 
-    f.render_widget(paragraph, area);
+    for (row, samples) in row_sparklines.into_iter().enumerate() {
+        let Some(data) = samples else { continue };
+        let row = row as u16;
+        if row >= trend_area.height {
+            break;
+        }
+        let row_area = Rect {
+            x: trend_area.x,
+            y: trend_area.y + row,
+            width: trend_width as u16,
+            height: 1,
+        };
+        let sparkline = Sparkline::default()
+            .data(&data)
+            .max(100)
+            .style(Style::default().fg(theme.highlight));
+        f.render_widget(sparkline, row_area);
+    }
 }
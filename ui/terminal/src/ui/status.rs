@@ -1,6 +1,5 @@
 use crate::state::AppState;
 use crate::types::ConnectionStatus;
-use crate::ui::theme::Theme;
 use chrono::Utc;
 use ratatui::{
     layout::Rect,
@@ -10,15 +9,44 @@ use ratatui::{
     Frame,
 };
 
+/// Min/avg/max and jitter (population stddev) over a latency sample set.
+struct LatencyStats {
+    min: u64,
+    max: u64,
+    avg: u64,
+    jitter: f64,
+}
+
+fn latency_stats(samples: &[u64]) -> Option<LatencyStats> {
+    if samples.is_empty() {
+        return None;
+    }
+
+    let min = *samples.iter().min().unwrap();
+    let max = *samples.iter().max().unwrap();
+    let avg = samples.iter().sum::<u64>() as f64 / samples.len() as f64;
+    let variance = samples
+        .iter()
+        .map(|&v| {
+            let delta = v as f64 - avg;
+            delta * delta
+        })
+        .sum::<f64>()
+        / samples.len() as f64;
+
+    Some(LatencyStats {
+        min,
+        max,
+        avg: avg.round() as u64,
+        jitter: variance.sqrt(),
+    })
+}
+
 pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
-    let theme = Theme::default();
+    let theme = &state.theme;
 
-    let status_symbol = match state.connection_status {
-        ConnectionStatus::Connected => "●",
-        ConnectionStatus::Connecting => "◐",
-        ConnectionStatus::Disconnected => "○",
-        ConnectionStatus::Error => "✖",
-    };
+    let status_symbol =
+        theme.connection_status_symbol(&state.connection_status, state.enhanced_graphics);
 
     let status_text = match state.connection_status {
         ConnectionStatus::Connected => "Connected",
@@ -27,21 +55,54 @@ pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
         ConnectionStatus::Error => "Error",
     };
 
-    let latency_text = if state.connection_status == ConnectionStatus::Connected {
-        format!(" ({}ms)", state.connection_latency_ms)
-    } else {
-        String::new()
-    };
+    // Latency text, sparkline, and min/avg/max/jitter readout -- all empty
+    // unless currently connected and at least one sample has landed.
+    let mut latency_spans = Vec::new();
+    if state.connection_status == ConnectionStatus::Connected {
+        let latency_style = theme.latency_style(
+            state.connection_latency_ms,
+            state.config.ui.latency_warn_threshold_ms,
+            state.config.ui.latency_critical_threshold_ms,
+        );
+        latency_spans.push(Span::raw(" ("));
+        latency_spans.push(Span::styled(
+            format!("{}ms", state.connection_latency_ms),
+            latency_style,
+        ));
+        latency_spans.push(Span::raw(")"));
+
+        let samples: Vec<u64> = state.latency_history.iter().copied().collect();
+        if let Some(stats) = latency_stats(&samples) {
+            let sparkline = theme.latency_sparkline(&samples, state.enhanced_graphics);
+            latency_spans.push(Span::raw(" "));
+            latency_spans.push(Span::styled(sparkline, Style::default().fg(theme.text_dim)));
+            latency_spans.push(Span::styled(
+                format!(
+                    " min:{} avg:{} max:{} jitter:{:.1}",
+                    stats.min, stats.avg, stats.max, stats.jitter
+                ),
+                Style::default().fg(theme.text_dim),
+            ));
+        }
+    }
 
     let stale_indicator = if state.is_stale() {
-        Span::styled(" ⚠ STALE DATA", Style::default().fg(theme.warning))
+        Span::styled(
+            theme.stale_data_label(state.enhanced_graphics),
+            Style::default().fg(theme.warning),
+        )
     } else {
         Span::raw("")
     };
 
     let current_time = Utc::now().format("%H:%M:%S UTC").to_string();
 
-    let line = Line::from(vec![
+    let protocol_text = state
+        .capabilities
+        .map(|c| format!(" │ proto v{}.{}", c.protocol_version, c.feature_version))
+        .unwrap_or_default();
+
+    let mut spans = vec![
         Span::styled(
             " B25 Trading System ",
             Style::default().fg(theme.highlight),
@@ -53,15 +114,18 @@ pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
         ),
         Span::raw(" "),
         Span::styled(
-            format!("{}{}", status_text, latency_text),
+            status_text,
             theme.connection_status_style(&state.connection_status),
         ),
-        stale_indicator,
-        Span::raw(" │ "),
-        Span::styled(current_time, Style::default().fg(theme.text_dim)),
-    ]);
+    ];
+    spans.extend(latency_spans);
+    spans.push(stale_indicator);
+    spans.push(Span::styled(protocol_text, Style::default().fg(theme.text_dim)));
+    spans.push(Span::raw(" │ "));
+    spans.push(Span::styled(current_time, Style::default().fg(theme.text_dim)));
 
-    let paragraph = Paragraph::new(line).style(Style::default().fg(theme.text).bg(Color::Black));
+    let paragraph =
+        Paragraph::new(Line::from(spans)).style(Style::default().fg(theme.text).bg(Color::Black));
 
     f.render_widget(paragraph, area);
 }
@@ -1,4 +1,7 @@
+use anyhow::Result;
 use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+use std::path::Path;
 
 #[derive(Debug, Clone)]
 pub struct Theme {
@@ -58,7 +61,139 @@ impl Default for Theme {
     }
 }
 
+/// Directory searched by `Theme::load` for named color scheme files.
+const THEMES_DIR: &str = "themes";
+
+/// On-disk representation of a `themes/<name>.yaml` file: each field is a
+/// raw color string (a named ANSI color like `"cyan"`, a 256-color index
+/// like `"214"`, or a truecolor `"#rrggbb"` hex value). Fields left out of
+/// the file keep `Theme::default()`'s value.
+#[derive(Debug, Default, Deserialize)]
+struct ThemeFile {
+    connected: Option<String>,
+    disconnected: Option<String>,
+    warning: Option<String>,
+    error: Option<String>,
+    profit: Option<String>,
+    loss: Option<String>,
+    neutral: Option<String>,
+    buy: Option<String>,
+    sell: Option<String>,
+    long: Option<String>,
+    short: Option<String>,
+    border: Option<String>,
+    border_focused: Option<String>,
+    text: Option<String>,
+    text_dim: Option<String>,
+    highlight: Option<String>,
+    background: Option<String>,
+}
+
+/// Parses a theme color string: `#rrggbb` truecolor, a bare `0`-`255`
+/// 256-color index, or a named ANSI color (case-insensitive).
+fn parse_color(raw: &str) -> Option<Color> {
+    let raw = raw.trim();
+
+    if let Some(hex) = raw.strip_prefix('#') {
+        if hex.len() == 6 {
+            let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+            let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+            let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+            return Some(Color::Rgb(r, g, b));
+        }
+        return None;
+    }
+
+    if let Ok(index) = raw.parse::<u8>() {
+        return Some(Color::Indexed(index));
+    }
+
+    match raw.to_lowercase().as_str() {
+        "black" => Some(Color::Black),
+        "red" => Some(Color::Red),
+        "green" => Some(Color::Green),
+        "yellow" => Some(Color::Yellow),
+        "blue" => Some(Color::Blue),
+        "magenta" => Some(Color::Magenta),
+        "cyan" => Some(Color::Cyan),
+        "gray" | "grey" => Some(Color::Gray),
+        "darkgray" | "dark_gray" | "darkgrey" => Some(Color::DarkGray),
+        "lightred" => Some(Color::LightRed),
+        "lightgreen" => Some(Color::LightGreen),
+        "lightyellow" => Some(Color::LightYellow),
+        "lightblue" => Some(Color::LightBlue),
+        "lightmagenta" => Some(Color::LightMagenta),
+        "lightcyan" => Some(Color::LightCyan),
+        "white" => Some(Color::White),
+        _ => None,
+    }
+}
+
 impl Theme {
+    /// Loads the named color scheme from `themes/<name>.yaml`, falling back
+    /// to `Theme::default()` (and logging a warning) when the name is
+    /// `"default"`, the file doesn't exist, or it fails to parse.
+    pub fn load(name: &str) -> Self {
+        if name.eq_ignore_ascii_case("default") || name.is_empty() {
+            return Self::default();
+        }
+
+        let path = Path::new(THEMES_DIR).join(format!("{name}.yaml"));
+        match Self::from_file(&path) {
+            Ok(theme) => theme,
+            Err(e) => {
+                tracing::warn!("Failed to load theme '{}' from {:?}: {}", name, path, e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Reads a theme file and applies it on top of `Theme::default()`,
+    /// leaving any color it doesn't specify (or fails to parse) untouched.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let file: ThemeFile = serde_yaml::from_str(&contents)?;
+
+        let mut theme = Self::default();
+        theme.apply(&file);
+        Ok(theme)
+    }
+
+    fn apply(&mut self, file: &ThemeFile) {
+        macro_rules! apply_field {
+            ($field:ident) => {
+                if let Some(raw) = &file.$field {
+                    match parse_color(raw) {
+                        Some(color) => self.$field = color,
+                        None => tracing::warn!(
+                            "Unrecognized theme color '{}' for `{}`",
+                            raw,
+                            stringify!($field)
+                        ),
+                    }
+                }
+            };
+        }
+
+        apply_field!(connected);
+        apply_field!(disconnected);
+        apply_field!(warning);
+        apply_field!(error);
+        apply_field!(profit);
+        apply_field!(loss);
+        apply_field!(neutral);
+        apply_field!(buy);
+        apply_field!(sell);
+        apply_field!(long);
+        apply_field!(short);
+        apply_field!(border);
+        apply_field!(border_focused);
+        apply_field!(text);
+        apply_field!(text_dim);
+        apply_field!(highlight);
+        apply_field!(background);
+    }
+
     pub fn profit_style(&self, value: f64) -> Style {
         if value > 0.0 {
             Style::default().fg(self.profit)
@@ -100,6 +235,55 @@ impl Theme {
         }
     }
 
+    /// Status bar latency text color: `connected` below `warn_ms`,
+    /// `warning` from `warn_ms` up to `critical_ms`, `error` at or above it.
+    pub fn latency_style(&self, latency_ms: u64, warn_ms: u64, critical_ms: u64) -> Style {
+        if latency_ms >= critical_ms {
+            Style::default().fg(self.error)
+        } else if latency_ms >= warn_ms {
+            Style::default().fg(self.warning)
+        } else {
+            Style::default().fg(self.connected)
+        }
+    }
+
+    /// Renders `samples` (oldest first) as a compact inline sparkline,
+    /// scaled between the samples' own min and max rather than a fixed
+    /// range, so a quiet connection's small jitter is still visible.
+    /// Falls back to a flat dashed line when `enhanced_graphics` is off.
+    pub fn latency_sparkline(&self, samples: &[u64], enhanced_graphics: bool) -> String {
+        if samples.is_empty() {
+            return String::new();
+        }
+
+        if !enhanced_graphics {
+            return "-".repeat(samples.len());
+        }
+
+        const TICKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        let min = *samples.iter().min().unwrap();
+        let max = *samples.iter().max().unwrap();
+        let range = (max - min).max(1) as f64;
+
+        samples
+            .iter()
+            .map(|&v| {
+                let scaled = (v - min) as f64 / range * (TICKS.len() - 1) as f64;
+                TICKS[(scaled.round() as usize).min(TICKS.len() - 1)]
+            })
+            .collect()
+    }
+
+    pub fn log_level_style(&self, level: &crate::logging::LogLevel) -> Style {
+        use crate::logging::LogLevel::*;
+        match level {
+            Error => Style::default().fg(self.error),
+            Warn => Style::default().fg(self.warning),
+            Info => Style::default().fg(self.text),
+            Debug => Style::default().fg(self.text_dim),
+        }
+    }
+
     pub fn connection_status_style(&self, status: &crate::types::ConnectionStatus) -> Style {
         match status {
             crate::types::ConnectionStatus::Connected => Style::default().fg(self.connected),
@@ -108,4 +292,52 @@ impl Theme {
             crate::types::ConnectionStatus::Error => Style::default().fg(self.error),
         }
     }
+
+    /// Connection indicator: a single Unicode dot when `enhanced_graphics`
+    /// is set, otherwise a three-character bracketed ASCII form (`[+]`), so
+    /// the status bar text is two columns wider in ASCII mode.
+    pub fn connection_status_symbol(
+        &self,
+        status: &crate::types::ConnectionStatus,
+        enhanced_graphics: bool,
+    ) -> &'static str {
+        use crate::types::ConnectionStatus::*;
+        if enhanced_graphics {
+            match status {
+                Connected => "●",
+                Connecting => "◐",
+                Disconnected => "○",
+                Error => "✖",
+            }
+        } else {
+            match status {
+                Connected => "[+]",
+                Connecting => "[~]",
+                Disconnected => "[ ]",
+                Error => "[x]",
+            }
+        }
+    }
+
+    /// Stale-data warning label, shown in the status bar.
+    pub fn stale_data_label(&self, enhanced_graphics: bool) -> &'static str {
+        if enhanced_graphics {
+            " ⚠ STALE DATA"
+        } else {
+            " !STALE"
+        }
+    }
+
+    /// Renders a `bar_length`-out-of-`max_length` strength bar for the AI
+    /// Signals panel: `bar_length` Unicode blocks when `enhanced_graphics`
+    /// is set, otherwise `#`-filled/`=`-empty ASCII always `max_length`
+    /// wide. The caller's fixed `{:<12}` column pads either form.
+    pub fn strength_bar(&self, bar_length: usize, max_length: usize, enhanced_graphics: bool) -> String {
+        if enhanced_graphics {
+            "█".repeat(bar_length)
+        } else {
+            let filled = bar_length.min(max_length);
+            format!("{}{}", "#".repeat(filled), "=".repeat(max_length - filled))
+        }
+    }
 }
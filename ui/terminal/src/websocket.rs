@@ -1,16 +1,45 @@
 use crate::config::ConnectionConfig;
+use crate::execution::OrderAck;
 use crate::state::StateUpdate;
 use crate::types::*;
+use common::Decimal;
 use anyhow::Result;
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::time::timeout;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
+/// Identifies this client to the dashboard server during the handshake.
+const CLIENT_NAME: &str = "b25-terminal-ui";
+
+/// This client's wire protocol / feature version. Bump `PROTOCOL_VERSION`
+/// when the message schema changes in a way an older server can't parse;
+/// bump `FEATURE_VERSION` for additive, backwards-compatible changes.
+const PROTOCOL_VERSION: u32 = 2;
+const FEATURE_VERSION: u32 = 2;
+
+/// Oldest server protocol version this client can still speak to. Below
+/// this the message schemas have diverged too far to negotiate down to.
+const MIN_COMPATIBLE_PROTOCOL_VERSION: u32 = 1;
+
+/// How long to wait for the server's `hello_ack` before giving up on the
+/// connection attempt.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type")]
 pub enum DashboardMessage {
+    #[serde(rename = "hello_ack")]
+    HelloAck {
+        server_name: String,
+        protocol_version: u32,
+        feature_version: u32,
+    },
+
     #[serde(rename = "positions")]
     Positions { data: Vec<Position> },
 
@@ -26,31 +55,85 @@ pub enum DashboardMessage {
     #[serde(rename = "signals")]
     Signals { data: Vec<Signal> },
 
+    #[serde(rename = "candles")]
+    Candles { data: Vec<Candle> },
+
     #[serde(rename = "alerts")]
     Alerts { data: Vec<Alert> },
 
     #[serde(rename = "ping")]
     Ping { timestamp: i64 },
+
+    #[serde(rename = "order_ack")]
+    OrderAck {
+        client_order_id: String,
+        order_id: String,
+    },
+
+    #[serde(rename = "order_reject")]
+    OrderReject {
+        client_order_id: String,
+        reason: String,
+    },
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone)]
 #[serde(tag = "type")]
 pub enum ClientMessage {
+    #[serde(rename = "hello")]
+    Hello {
+        client_name: String,
+        protocol_version: u32,
+        feature_version: u32,
+    },
+
     #[serde(rename = "subscribe")]
     Subscribe { channels: Vec<String> },
 
     #[serde(rename = "pong")]
     Pong { timestamp: i64 },
+
+    #[serde(rename = "submit_order")]
+    SubmitOrder {
+        client_order_id: String,
+        symbol: String,
+        side: OrderSide,
+        order_type: OrderType,
+        price: Decimal,
+        size: Decimal,
+    },
+
+    #[serde(rename = "cancel_order")]
+    CancelOrder {
+        client_order_id: String,
+        order_id: String,
+    },
 }
 
+/// Pending client-order-id -> waiter, resolved once an ack or reject for
+/// that id arrives over the dashboard socket.
+pub type PendingOrders = Arc<Mutex<HashMap<String, oneshot::Sender<OrderAck>>>>;
+
 pub struct WsClient {
     config: ConnectionConfig,
     state_tx: mpsc::Sender<StateUpdate>,
+    outbound_rx: Mutex<mpsc::Receiver<ClientMessage>>,
+    pending: PendingOrders,
 }
 
 impl WsClient {
-    pub fn new(config: ConnectionConfig, state_tx: mpsc::Sender<StateUpdate>) -> Self {
-        Self { config, state_tx }
+    pub fn new(
+        config: ConnectionConfig,
+        state_tx: mpsc::Sender<StateUpdate>,
+        outbound_rx: mpsc::Receiver<ClientMessage>,
+        pending: PendingOrders,
+    ) -> Self {
+        Self {
+            config,
+            state_tx,
+            outbound_rx: Mutex::new(outbound_rx),
+            pending,
+        }
     }
 
     pub async fn connect_with_retry(&self) -> Result<()> {
@@ -101,6 +184,66 @@ impl WsClient {
         let (ws_stream, _) = connect_async(&self.config.dashboard_url).await?;
         let (mut write, mut read) = ws_stream.split();
 
+        // Handshake: negotiate protocol/feature version before subscribing
+        // to any data channels, so a schema mismatch is caught up front
+        // instead of surfacing as a parse error later.
+        let hello = ClientMessage::Hello {
+            client_name: CLIENT_NAME.to_string(),
+            protocol_version: PROTOCOL_VERSION,
+            feature_version: FEATURE_VERSION,
+        };
+        write.send(Message::Text(serde_json::to_string(&hello)?)).await?;
+
+        let (server_name, server_protocol_version, server_feature_version) = loop {
+            match timeout(HANDSHAKE_TIMEOUT, read.next()).await {
+                Ok(Some(Ok(Message::Text(text)))) => {
+                    match serde_json::from_str::<DashboardMessage>(&text) {
+                        Ok(DashboardMessage::HelloAck {
+                            server_name,
+                            protocol_version,
+                            feature_version,
+                        }) => break (server_name, protocol_version, feature_version),
+                        Ok(_) => continue,
+                        Err(e) => {
+                            return Err(anyhow::anyhow!("malformed handshake response: {e}"))
+                        }
+                    }
+                }
+                Ok(Some(Ok(_))) => continue,
+                Ok(Some(Err(e))) => return Err(e.into()),
+                Ok(None) => return Err(anyhow::anyhow!("connection closed during handshake")),
+                Err(_) => return Err(anyhow::anyhow!("timed out waiting for server hello_ack")),
+            }
+        };
+
+        if server_protocol_version < MIN_COMPATIBLE_PROTOCOL_VERSION {
+            return Err(anyhow::anyhow!(
+                "server '{server_name}' speaks protocol v{server_protocol_version}, older than the minimum v{MIN_COMPATIBLE_PROTOCOL_VERSION} this client supports"
+            ));
+        }
+
+        if server_protocol_version < PROTOCOL_VERSION {
+            let _ = self
+                .state_tx
+                .send(StateUpdate::Alerts(vec![Alert {
+                    id: common::generate_request_id(),
+                    level: AlertLevel::Warning,
+                    message: format!(
+                        "Connected to '{server_name}' on protocol v{server_protocol_version} (client supports v{PROTOCOL_VERSION}); some panels may be disabled"
+                    ),
+                    timestamp: chrono::Utc::now(),
+                }]))
+                .await;
+        }
+
+        let _ = self
+            .state_tx
+            .send(StateUpdate::Capabilities(ServerCapabilities {
+                protocol_version: server_protocol_version,
+                feature_version: server_feature_version,
+            }))
+            .await;
+
         // Send subscription message
         let subscribe_msg = ClientMessage::Subscribe {
             channels: vec![
@@ -109,6 +252,7 @@ impl WsClient {
                 "orderbook".to_string(),
                 "fills".to_string(),
                 "signals".to_string(),
+                "candles".to_string(),
                 "alerts".to_string(),
             ],
         };
@@ -127,62 +271,107 @@ impl WsClient {
             ))
             .await;
 
-        // Message processing loop
-        while let Some(msg) = read.next().await {
-            match msg {
-                Ok(Message::Text(text)) => {
-                    let start = std::time::Instant::now();
+        // Message processing loop: interleave inbound dashboard messages with
+        // outbound order requests queued by the execution client, so both
+        // share this one socket.
+        let mut outbound = self.outbound_rx.lock().await;
+        loop {
+            tokio::select! {
+                msg = read.next() => {
+                    let msg = match msg {
+                        Some(msg) => msg,
+                        None => return Ok(()),
+                    };
 
-                    match serde_json::from_str::<DashboardMessage>(&text) {
-                        Ok(dashboard_msg) => {
-                            self.handle_message(dashboard_msg).await?;
-
-                            let latency = start.elapsed().as_millis() as u64;
-                            let _ = self
-                                .state_tx
-                                .send(StateUpdate::ConnectionStatus(
-                                    ConnectionStatus::Connected,
-                                    latency,
-                                ))
-                                .await;
+                    match msg {
+                        Ok(Message::Text(text)) => {
+                            let start = std::time::Instant::now();
+
+                            match serde_json::from_str::<DashboardMessage>(&text) {
+                                Ok(dashboard_msg) => {
+                                    self.handle_message(dashboard_msg).await?;
+
+                                    let latency = start.elapsed().as_millis() as u64;
+                                    let _ = self
+                                        .state_tx
+                                        .send(StateUpdate::ConnectionStatus(
+                                            ConnectionStatus::Connected,
+                                            latency,
+                                        ))
+                                        .await;
+                                }
+                                Err(e) => {
+                                    tracing::error!("Failed to parse message: {}", e);
+                                    tracing::debug!("Message content: {}", text);
+                                }
+                            }
+                        }
+                        Ok(Message::Close(_)) => {
+                            tracing::warn!("WebSocket closed by server");
+                            return Err(anyhow::anyhow!("Connection closed"));
+                        }
+                        Ok(Message::Ping(data)) => {
+                            write.send(Message::Pong(data)).await?;
                         }
+                        Ok(_) => {}
                         Err(e) => {
-                            tracing::error!("Failed to parse message: {}", e);
-                            tracing::debug!("Message content: {}", text);
+                            tracing::error!("WebSocket error: {}", e);
+                            return Err(e.into());
                         }
                     }
                 }
-                Ok(Message::Close(_)) => {
-                    tracing::warn!("WebSocket closed by server");
-                    return Err(anyhow::anyhow!("Connection closed"));
-                }
-                Ok(Message::Ping(data)) => {
-                    write.send(Message::Pong(data)).await?;
-                }
-                Ok(_) => {}
-                Err(e) => {
-                    tracing::error!("WebSocket error: {}", e);
-                    return Err(e.into());
+
+                Some(client_msg) = outbound.recv() => {
+                    let msg_json = serde_json::to_string(&client_msg)?;
+                    write.send(Message::Text(msg_json)).await?;
                 }
             }
         }
-
-        Ok(())
     }
 
     async fn handle_message(&self, msg: DashboardMessage) -> Result<()> {
         let update = match msg {
+            DashboardMessage::HelloAck { .. } => {
+                // Already consumed during the handshake in `connect`.
+                return Ok(());
+            }
             DashboardMessage::Positions { data } => StateUpdate::Positions(data),
             DashboardMessage::Orders { data } => StateUpdate::Orders(data),
             DashboardMessage::OrderBook { data } => StateUpdate::OrderBook(data),
             DashboardMessage::Fills { data } => StateUpdate::Fills(data),
             DashboardMessage::Signals { data } => StateUpdate::Signals(data),
+            DashboardMessage::Candles { data } => StateUpdate::Candles(data),
             DashboardMessage::Alerts { data } => StateUpdate::Alerts(data),
             DashboardMessage::Ping { timestamp } => {
                 // Handle ping/pong if needed
                 tracing::trace!("Received ping: {}", timestamp);
                 return Ok(());
             }
+            DashboardMessage::OrderAck {
+                client_order_id,
+                order_id,
+            } => {
+                if let Some(waiter) = self.pending.lock().await.remove(&client_order_id) {
+                    let _ = waiter.send(OrderAck::Accepted { order_id });
+                }
+                return Ok(());
+            }
+            DashboardMessage::OrderReject {
+                client_order_id,
+                reason,
+            } => {
+                if let Some(waiter) = self.pending.lock().await.remove(&client_order_id) {
+                    let _ = waiter.send(OrderAck::Rejected {
+                        reason: reason.clone(),
+                    });
+                }
+                StateUpdate::Alerts(vec![Alert {
+                    id: client_order_id,
+                    level: AlertLevel::Error,
+                    message: format!("Order rejected: {reason}"),
+                    timestamp: chrono::Utc::now(),
+                }])
+            }
         };
 
         self.state_tx.send(update).await?;